@@ -1,21 +1,14 @@
 use aoc::grid::Grid;
 
-fn check(grid: &Grid) -> usize {
-    0
-}
-
 fn part1(lines: Vec<String>) -> usize {
-    let grid = Grid::from_vec(&lines);
-    dbg!(grid
-        .rows()
-        .into_iter()
-        .map(|x| String::from_utf8(x).unwrap())
-        .collect::<Vec<String>>());
-    check(&grid)
+    Grid::from_vec(&lines).find_word(b"XMAS").len()
 }
 
 fn part2(lines: Vec<String>) -> usize {
-    0
+    let pattern = Grid::parse("M.S\n.A.\nM.S");
+    Grid::from_vec(&lines)
+        .find_pattern_oriented(&pattern, &b'.')
+        .len()
 }
 
 #[cfg(test)]
@@ -44,12 +37,23 @@ MXMXAXMASX
     //     dbg!(part1(aoc::lines_file("./input/day4.txt")));
     // }
 
-    // #[test]
-    // fn test_part2() {
-    //     let t = r"";
-    //     let r = part2(aoc::lines_str(t));
-    //     assert_eq!(r, 0);
-    // }
+    #[test]
+    fn test_part2() {
+        let t = "
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+        let r = part2(aoc::lines_str(t));
+        assert_eq!(r, 9);
+    }
 
     // #[test]
     // fn test_part2_real() {
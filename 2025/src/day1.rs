@@ -8,12 +8,6 @@ struct Knob {
 }
 
 impl Knob {
-    /// python style modulus, returns absolute value for things like -18 % 100, which results in 82
-    /// and not in -18, as rust defines mod
-    fn m(a: i64, b: i64) -> i64 {
-        ((a % b) + b) % b
-    }
-
     fn left(&mut self, step: usize) {
         let step = step as i64;
         self.inner_zero_passes += if self.pos == 0 {
@@ -24,7 +18,7 @@ impl Knob {
             1 + ((step - self.pos) / SIZE) as usize
         };
 
-        self.pos = Knob::m(self.pos - step as i64, SIZE);
+        self.pos = (self.pos - step).rem_euclid(SIZE);
     }
 
     fn right(&mut self, step: usize) {
@@ -37,7 +31,7 @@ impl Knob {
         } else {
             1 + ((step - dist) / SIZE) as usize
         };
-        self.pos = Knob::m(self.pos + step as i64, SIZE);
+        self.pos = (self.pos + step).rem_euclid(SIZE);
     }
 }
 
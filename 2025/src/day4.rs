@@ -1,63 +1,35 @@
-use aoc::point::{self, Point};
+use aoc::{
+    automaton::{Automaton, Update},
+    point,
+};
 
 fn part_1(lines: Vec<String>) -> usize {
     let grid = aoc::grid::Grid::from_vec(&lines);
-    let mut count = 0;
-
-    for y in 0..grid.height {
-        for x in 0..grid.width {
-            let p = Point::new(x, y);
-            if grid.get(p.x as usize, p.y as usize) != Some(b'@') {
-                continue;
-            }
-
-            let neighbors = point::DIAGONAL
+    grid.positions(|&c| c == b'@')
+        .filter(|&p| {
+            point::DIAGONAL
                 .iter()
-                .filter(|&&dir| grid.get((p + dir).x as usize, (p + dir).y as usize) == Some(b'@'))
-                .count();
-
-            if neighbors < 4 {
-                count += 1;
-            }
-        }
-    }
-
-    count
+                .filter(|&&dir| grid.get_point(p + dir) == Some(b'@'))
+                .count()
+                < 4
+        })
+        .count()
 }
 
 fn part_2(lines: Vec<String>) -> usize {
-    let mut grid = aoc::grid::Grid::from_vec(&lines);
-    let mut removed = 0;
-
-    loop {
-        let prev = removed;
-        for y in 0..grid.height {
-            for x in 0..grid.width {
-                let p = Point::new(x, y);
-
-                if grid.get(p.x as usize, p.y as usize) != Some(b'@') {
-                    continue;
-                }
-
-                let neighbors = point::DIAGONAL
-                    .iter()
-                    .filter(|&&dir| {
-                        grid.get((p + dir).x as usize, (p + dir).y as usize) == Some(b'@')
-                    })
-                    .count();
+    let grid = aoc::grid::Grid::from_vec(&lines);
+    let before = grid.count(b'@');
 
-                if neighbors < 4 {
-                    grid[p] = b'.';
-                    removed += 1;
-                }
-            }
-        }
-        if prev == removed {
-            break;
+    let mut automaton = Automaton::new(grid, Update::InPlace, |&c, neighbors: &[u8]| {
+        if c == b'@' && neighbors.iter().filter(|&&n| n == b'@').count() < 4 {
+            b'.'
+        } else {
+            c
         }
-    }
+    });
+    automaton.run_until_stable();
 
-    removed
+    before - automaton.grid.count(b'@')
 }
 
 #[cfg(test)]
@@ -0,0 +1,187 @@
+use crate::{
+    grid::Grid,
+    point::{self, Point},
+};
+
+/// How an Automaton applies its rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// double buffered, every cell of a generation sees the previous generation (game of life)
+    Synchronous,
+    /// cells are updated in reading order and later cells see earlier changes of the same sweep
+    InPlace,
+}
+
+/// Cellular automaton over a Grid. `rule` receives a cell and the values of its in bounds
+/// neighbors and returns the cells next value.
+///
+/// Only cells in the neighborhood of a change are re-evaluated in the next generation, so rules
+/// must only depend on the cell and its neighbors
+pub struct Automaton<T, F> {
+    pub grid: Grid<T>,
+    /// offsets making up a cells neighborhood, defaults to all 8 neighbors
+    pub neighborhood: &'static [Point],
+    pub mode: Update,
+    pub generation: usize,
+    rule: F,
+    dirty: Vec<Point>,
+    queued: Grid<bool>,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + PartialEq,
+    F: FnMut(&T, &[T]) -> T,
+{
+    pub fn new(grid: Grid<T>, mode: Update, rule: F) -> Self {
+        let dirty = grid.iter().map(|(p, _)| p).collect();
+        let queued = grid.map(|_, _| true);
+        Automaton {
+            grid,
+            neighborhood: &point::DIAGONAL,
+            mode,
+            generation: 0,
+            rule,
+            dirty,
+            queued,
+        }
+    }
+
+    /// computes the next generation, returns the number of cells that changed
+    pub fn step(&mut self) -> usize {
+        let mut work = std::mem::take(&mut self.dirty);
+        work.sort_by_key(|p| (p.y, p.x));
+        for &p in &work {
+            self.queued[p] = false;
+        }
+
+        let mut neighbors = Vec::with_capacity(self.neighborhood.len());
+        let mut changed = vec![];
+        let mut pending = vec![];
+        for p in work {
+            neighbors.clear();
+            neighbors.extend(
+                self.neighborhood
+                    .iter()
+                    .filter_map(|&dir| self.grid.get_ref(p + dir).cloned()),
+            );
+            let next = (self.rule)(&self.grid[p], &neighbors);
+            if next == self.grid[p] {
+                continue;
+            }
+            match self.mode {
+                Update::InPlace => self.grid[p] = next,
+                Update::Synchronous => pending.push(next),
+            }
+            changed.push(p);
+        }
+
+        if self.mode == Update::Synchronous {
+            for (&p, next) in changed.iter().zip(pending) {
+                self.grid[p] = next;
+            }
+        }
+
+        for &p in &changed {
+            self.mark(p);
+            // the cells that have p in their neighborhood
+            for &dir in self.neighborhood {
+                self.mark(p - dir);
+            }
+        }
+
+        self.generation += 1;
+        changed.len()
+    }
+
+    fn mark(&mut self, p: Point) {
+        if self.grid.contains(p) && !self.queued[p] {
+            self.queued[p] = true;
+            self.dirty.push(p);
+        }
+    }
+
+    /// runs up to `generations` steps, stopping early at a fixpoint. Returns the number of steps
+    /// that changed at least one cell
+    pub fn run(&mut self, generations: usize) -> usize {
+        for i in 0..generations {
+            if self.step() == 0 {
+                return i;
+            }
+        }
+        generations
+    }
+
+    /// steps until a generation changes nothing, returns the number of steps that changed at
+    /// least one cell
+    pub fn run_until_stable(&mut self) -> usize {
+        self.run(usize::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(&cell: &u8, neighbors: &[u8]) -> u8 {
+        match (cell, neighbors.iter().filter(|&&n| n == b'#').count()) {
+            (b'#', 2 | 3) | (b'.', 3) => b'#',
+            _ => b'.',
+        }
+    }
+
+    #[test]
+    fn test_blinker() {
+        let mut automaton = Automaton::new(
+            Grid::parse(".....\n..#..\n..#..\n..#..\n....."),
+            Update::Synchronous,
+            life,
+        );
+        assert_eq!(automaton.step(), 4);
+        assert_eq!(
+            automaton.grid.rows(),
+            Grid::parse(".....\n.....\n.###.\n.....\n.....").rows()
+        );
+        assert_eq!(automaton.run(3), 3);
+        assert_eq!(automaton.generation, 4);
+        assert_eq!(
+            automaton.grid.rows(),
+            Grid::parse(".....\n..#..\n..#..\n..#..\n.....").rows()
+        );
+    }
+
+    #[test]
+    fn test_still_life() {
+        let block = Grid::parse("....\n.##.\n.##.\n....");
+        let mut automaton = Automaton::new(block.clone(), Update::Synchronous, life);
+        assert_eq!(automaton.run_until_stable(), 0);
+        assert_eq!(automaton.grid, block);
+    }
+
+    #[test]
+    fn test_in_place() {
+        // every cell copies its left neighbor, in place this floods the row in a single sweep
+        let mut automaton = Automaton::new(Grid::parse("#...."), Update::InPlace, |&c, n| {
+            if n.contains(&b'#') {
+                b'#'
+            } else {
+                c
+            }
+        });
+        automaton.neighborhood = &[point::LEFT];
+        assert_eq!(automaton.step(), 4);
+        assert_eq!(automaton.grid.count(b'#'), 5);
+
+        let mut automaton = Automaton::new(Grid::parse("#...."), Update::Synchronous, |&c, n| {
+            if n.contains(&b'#') {
+                b'#'
+            } else {
+                c
+            }
+        });
+        automaton.neighborhood = &[point::LEFT, point::RIGHT];
+        assert_eq!(automaton.step(), 1);
+        assert_eq!(automaton.run_until_stable(), 3);
+        assert_eq!(automaton.grid.count(b'#'), 5);
+    }
+}
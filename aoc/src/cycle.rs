@@ -0,0 +1,174 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A detected cycle: the state after `start` steps is the first one to repeat, it reappears
+/// every `length` steps from then on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// the step in [0, start + length) whose state equals the state after `n` steps
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// applies `step` to `initial` until a state repeats, remembering every state in a hash map.
+/// Requires `step` to be deterministic and the state space to be finite
+pub fn find_cycle<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(&start) = seen.get(&state) {
+            return Cycle {
+                start,
+                length: i - start,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!("state space exhausted usize")
+}
+
+/// Brent's cycle detection, same as find_cycle but only keeps a constant number of states
+/// around and does not require Hash, at the cost of calling `step` more often
+pub fn brent<S, F>(initial: S, mut step: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // find the cycle length by teleporting the tortoise to the hare at powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // hare runs `length` steps ahead, walking both until they meet yields the cycle start
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// the state after applying `step` `n` times to `initial`, extrapolated via the first cycle
+/// instead of simulating all `n` steps
+pub fn state_at<S, F>(initial: S, mut step: F, n: usize) -> S
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i - start,
+            };
+            return history.swap_remove(cycle.reduce(n));
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        history.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    // 0 1 2 3 4 5 6 3 4 5 6 ...
+    fn tail(&n: &u32) -> u32 {
+        if n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let expected = Cycle {
+            start: 3,
+            length: 4,
+        };
+        assert_eq!(find_cycle(0, tail), expected);
+        assert_eq!(brent(0, tail), expected);
+        assert_eq!(
+            brent(5, |&n: &u32| (n + 1) % 7),
+            Cycle {
+                start: 0,
+                length: 7
+            }
+        );
+        assert_eq!(expected.reduce(2), 2);
+        assert_eq!(expected.reduce(8), 4);
+    }
+
+    #[test]
+    fn test_state_at() {
+        assert_eq!(state_at(0, tail, 0), 0);
+        assert_eq!(state_at(0, tail, 5), 5);
+        assert_eq!(state_at(0, tail, 7), 3);
+        assert_eq!(
+            state_at(0, tail, 1_000_000_000),
+            3 + (1_000_000_000 - 3) % 4
+        );
+    }
+
+    #[test]
+    fn test_grid_state() {
+        // rotating a grid cycles with length 4
+        let grid = Grid::parse("ab\ncd");
+        let rotate = |g: &Grid| {
+            let mut g = g.clone();
+            g.rotate_right();
+            g
+        };
+        assert_eq!(
+            find_cycle(grid.clone(), rotate),
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+
+        let mut expected = grid.clone();
+        expected.rotate_right();
+        assert_eq!(state_at(grid, rotate, 1_000_000_001), expected);
+    }
+}
@@ -0,0 +1,294 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::point::{self, Point};
+
+/// One of the four orthogonal directions, with UP being towards smaller y
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// One of the eight directions of a king's move, orthogonals and diagonals
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+/// Input that does not name a direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirectionError {
+    pub input: String,
+}
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a direction", self.input)
+    }
+}
+
+impl Error for ParseDirectionError {}
+
+impl Direction {
+    /// all directions, clockwise starting at Up like point::CARDINAL
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        Direction::ALL.into_iter()
+    }
+
+    /// rotates 90 degrees counterclockwise
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// rotates 90 degrees clockwise
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// the unit step in this direction
+    pub fn delta(self) -> Point {
+        point::CARDINAL[self as usize]
+    }
+
+    /// the direction of a unit step, None for anything else
+    pub fn from_delta(delta: Point) -> Option<Self> {
+        Direction::iter().find(|d| d.delta() == delta)
+    }
+
+    /// the arrow drawing this direction, one of `^>v<`
+    pub fn arrow(self) -> char {
+        ['^', '>', 'v', '<'][self as usize]
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(d: Direction) -> Point {
+        d.delta()
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    /// accepts arrows `^v<>`, `UDLR` and compass letters `NESW`, case insensitive
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c.to_ascii_uppercase() {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            'V' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError {
+                input: c.to_string(),
+            }),
+        }
+    }
+}
+
+impl TryFrom<u8> for Direction {
+    type Error = ParseDirectionError;
+
+    /// same as the char conversion, for bytes out of a Grid
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        Direction::try_from(c as char)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    /// a single direction character, or one of the words up, down, left, right
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDirectionError {
+            input: s.to_string(),
+        };
+        let s = s.trim();
+        match s.to_ascii_lowercase().as_str() {
+            "up" => return Ok(Direction::Up),
+            "right" => return Ok(Direction::Right),
+            "down" => return Ok(Direction::Down),
+            "left" => return Ok(Direction::Left),
+            _ => (),
+        }
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c).map_err(|_| err()),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl Direction8 {
+    /// all directions, clockwise starting at Up
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn iter() -> impl Iterator<Item = Direction8> {
+        Direction8::ALL.into_iter()
+    }
+
+    /// rotates 45 degrees counterclockwise
+    pub fn turn_left(self) -> Self {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// rotates 45 degrees clockwise
+    pub fn turn_right(self) -> Self {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    /// the unit step in this direction, diagonals move on both axes
+    pub fn delta(self) -> Point {
+        let i = self as usize;
+        if i.is_multiple_of(2) {
+            point::CARDINAL[i / 2]
+        } else {
+            point::CARDINAL[i / 2] + point::CARDINAL[(i / 2 + 1) % 4]
+        }
+    }
+
+    /// the direction of a king's move, None for anything else
+    pub fn from_delta(delta: Point) -> Option<Self> {
+        Direction8::iter().find(|d| d.delta() == delta)
+    }
+
+    /// the orthogonal direction, None for diagonals
+    pub fn cardinal(self) -> Option<Direction> {
+        let i = self as usize;
+        i.is_multiple_of(2).then(|| Direction::ALL[i / 2])
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        Direction8::ALL[d as usize * 2]
+    }
+}
+
+impl From<Direction8> for Point {
+    fn from(d: Direction8) -> Point {
+        d.delta()
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    /// compass names N, NE, E, SE, S, SW, W, NW, case insensitive, or anything Direction parses
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_uppercase().as_str() {
+            "NE" => Ok(Direction8::UpRight),
+            "SE" => Ok(Direction8::DownRight),
+            "SW" => Ok(Direction8::DownLeft),
+            "NW" => Ok(Direction8::UpLeft),
+            _ => s.parse::<Direction>().map(Direction8::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn test_turns() {
+        for d in Direction::iter() {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.delta() + d.opposite().delta(), point::ORIGIN);
+            assert_eq!(Direction::from_delta(d.delta()), Some(d));
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Point::from(Direction::Left), point::LEFT);
+
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownLeft.opposite(), Direction8::UpRight);
+        assert_eq!(
+            Direction8::iter()
+                .map(Direction8::delta)
+                .collect::<Vec<_>>(),
+            [1, 2, 4, 7, 6, 5, 3, 0].map(|i| point::DIAGONAL[i])
+        );
+        assert_eq!(Direction8::Left.cardinal(), Some(Direction::Left));
+        assert_eq!(Direction8::UpLeft.cardinal(), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let arrows = "^>v<".chars().map(Direction::try_from);
+        let letters = "URDL".chars().map(Direction::try_from);
+        let compass = "nesw".chars().map(Direction::try_from);
+        for parsed in [arrows, letters, compass] {
+            assert_eq!(
+                parsed.collect::<Result<Vec<_>, _>>(),
+                Ok(Direction::ALL.to_vec())
+            );
+        }
+        assert_eq!(Direction::try_from(b'v'), Ok(Direction::Down));
+        assert_eq!(" Left".parse(), Ok(Direction::Left));
+        assert!("x".parse::<Direction>().is_err());
+        assert!("UD".parse::<Direction>().is_err());
+        assert_eq!("sw".parse(), Ok(Direction8::DownLeft));
+        assert_eq!("E".parse(), Ok(Direction8::Right));
+        assert_eq!(
+            "Q".parse::<Direction8>(),
+            Err(ParseDirectionError {
+                input: "Q".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_guard_walk() {
+        let grid = Grid::parse(
+            "....#.....\n.........#\n..........\n..#.......\n.......#..\n\
+             ..........\n.#..^.....\n........#.\n#.........\n......#...",
+        );
+        let mut pos = grid.find(b'^').unwrap();
+        let mut dir = Direction::try_from(grid[pos]).unwrap();
+        let mut seen = std::collections::HashSet::from([pos]);
+        while let Some(next) = grid.get_point(pos + dir.delta()) {
+            if next == b'#' {
+                dir = dir.turn_right();
+            } else {
+                pos += dir.delta();
+                seen.insert(pos);
+            }
+        }
+        assert_eq!(seen.len(), 41);
+    }
+}
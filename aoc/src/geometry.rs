@@ -0,0 +1,142 @@
+use crate::{
+    direction::Direction,
+    point::{gcd, Point},
+};
+
+/// A closed polygon on the integer lattice, the last vertex connects back to the first
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<Point<i64>>,
+}
+
+impl Polygon {
+    pub fn new<I: IntoIterator<Item = Point<i64>>>(vertices: I) -> Self {
+        Polygon {
+            vertices: vertices.into_iter().collect(),
+        }
+    }
+
+    /// traces a path of (direction, length) moves starting at the origin, like a dig plan
+    pub fn from_instructions<I: IntoIterator<Item = (Direction, i64)>>(instructions: I) -> Self {
+        let mut pos = Point::new(0, 0);
+        let mut vertices = vec![pos];
+        for (dir, len) in instructions {
+            pos += dir.delta().cast::<i64>() * len;
+            vertices.push(pos);
+        }
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+
+    /// edges as (from, to), including the closing one
+    fn edges(&self) -> impl Iterator<Item = (Point<i64>, Point<i64>)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// twice the enclosed area by the shoelace formula, exact even for half integer areas
+    pub fn twice_area(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| a.x * b.y - b.x * a.y)
+            .sum::<i64>()
+            .abs()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.twice_area() as f64 / 2.0
+    }
+
+    /// number of lattice points on the edges
+    pub fn boundary(&self) -> i64 {
+        self.edges().map(|(a, b)| gcd(b.x - a.x, b.y - a.y)).sum()
+    }
+
+    /// number of lattice points strictly inside, by Pick's theorem A = I + B/2 - 1
+    pub fn interior(&self) -> i64 {
+        if self.twice_area() == 0 {
+            return 0;
+        }
+        (self.twice_area() - self.boundary()) / 2 + 1
+    }
+
+    /// number of lattice points inside or on the boundary, e.g. cells of a dug out lagoon
+    pub fn lattice_points(&self) -> i64 {
+        if self.vertices.is_empty() {
+            return 0;
+        }
+        if self.twice_area() == 0 {
+            // degenerate polygons are just their edges, which are walked there and back
+            return self.boundary() / 2 + 1;
+        }
+        self.interior() + self.boundary()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIG_PLAN: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_square() {
+        let square = Polygon::new([(0, 0), (4, 0), (4, 4), (0, 4)].map(|(x, y)| Point::new(x, y)));
+        assert_eq!(square.twice_area(), 32);
+        assert_eq!(square.area(), 16.0);
+        assert_eq!(square.boundary(), 16);
+        assert_eq!(square.interior(), 9);
+        assert_eq!(square.lattice_points(), 25);
+
+        let triangle = Polygon::new([(0, 0), (3, 0), (0, 3)].map(|(x, y)| Point::new(x, y)));
+        assert_eq!(triangle.area(), 4.5);
+        assert_eq!(triangle.boundary(), 9);
+        assert_eq!(triangle.interior(), 1);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let line = Polygon::from_instructions([(Direction::Right, 3), (Direction::Left, 3)]);
+        assert_eq!(line.twice_area(), 0);
+        assert_eq!(line.interior(), 0);
+        assert_eq!(line.lattice_points(), 4);
+        assert_eq!(Polygon::default().lattice_points(), 0);
+    }
+
+    #[test]
+    fn test_dig_plan() {
+        let small = Polygon::from_instructions(DIG_PLAN.lines().map(|l| {
+            let mut parts = l.split(' ');
+            let dir = parts.next().unwrap().parse::<Direction>().unwrap();
+            (dir, parts.next().unwrap().parse().unwrap())
+        }));
+        assert_eq!(small.lattice_points(), 62);
+
+        let huge = Polygon::from_instructions(DIG_PLAN.lines().map(|l| {
+            let hex = &l[l.len() - 7..l.len() - 1];
+            let dir = [
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+                Direction::Up,
+            ][(hex.as_bytes()[5] - b'0') as usize];
+            (dir, i64::from_str_radix(&hex[..5], 16).unwrap())
+        }));
+        assert_eq!(huge.lattice_points(), 952408144115);
+    }
+}
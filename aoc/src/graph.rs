@@ -0,0 +1,246 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A found path, `states` starts with the start state and ends with the goal state
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+/// Weighted directed graph stored as adjacency lists, plugs into dijkstra and astar via
+/// Graph::successors
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph<N: Eq + Hash> {
+    pub edges: HashMap<N, Vec<(N, u32)>>,
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Graph {
+            edges: HashMap::new(),
+        }
+    }
+
+    /// adds a directed edge, parallel edges are kept
+    pub fn add_edge(&mut self, from: N, to: N, weight: u32) {
+        self.edges.entry(to.clone()).or_default();
+        self.edges.entry(from).or_default().push((to, weight));
+    }
+
+    /// all nodes, including the ones without outgoing edges
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.edges.keys()
+    }
+
+    /// outgoing edges of `node` with their weights
+    pub fn successors(&self, node: &N) -> Vec<(N, u32)> {
+        self.edges.get(node).cloned().unwrap_or_default()
+    }
+
+    /// weight of the longest path from `start` to `goal` that visits no node twice, found by
+    /// exhaustive search, so only feasible for small graphs
+    pub fn longest_path(&self, start: &N, goal: &N) -> Option<u32> {
+        let ids = self
+            .edges
+            .keys()
+            .enumerate()
+            .map(|(i, n)| (n, i))
+            .collect::<HashMap<_, _>>();
+        let adjacency = self
+            .edges
+            .keys()
+            .map(|n| {
+                self.edges[n]
+                    .iter()
+                    .map(|(to, w)| (ids[to], *w))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        fn walk(
+            cur: usize,
+            goal: usize,
+            adjacency: &[Vec<(usize, u32)>],
+            visited: &mut [bool],
+        ) -> Option<u32> {
+            if cur == goal {
+                return Some(0);
+            }
+            visited[cur] = true;
+            let mut best = None;
+            for &(to, w) in &adjacency[cur] {
+                if !visited[to] {
+                    best = best.max(walk(to, goal, adjacency, visited).map(|l| l + w));
+                }
+            }
+            visited[cur] = false;
+            best
+        }
+
+        let (&start, &goal) = (ids.get(start)?, ids.get(goal)?);
+        walk(start, goal, &adjacency, &mut vec![false; adjacency.len()])
+    }
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Self {
+        Graph::new()
+    }
+}
+
+/// Dijkstra's algorithm from `start` until a state satisfying `goal` is popped.
+///
+/// `successors` yields every state reachable from the given one with the cost of the transition,
+/// the state can be anything hashable, for instance (Point, facing) for puzzles with turn
+/// penalties
+pub fn dijkstra<S, C, FN, IN, FG>(start: S, successors: FN, goal: FG) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(start, successors, goal, |_| C::default())
+}
+
+/// A* search, same as dijkstra, but guided by `heuristic`, which must never overestimate the
+/// remaining cost to the goal for the result to be optimal
+pub fn astar<S, C, FN, IN, FG, FH>(
+    start: S,
+    mut successors: FN,
+    mut goal: FG,
+    mut heuristic: FH,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+    FH: FnMut(&S) -> C,
+{
+    // states are interned, so the heap and the parent links only juggle indexes
+    let mut states = vec![start.clone()];
+    let mut index = HashMap::from([(start.clone(), 0)]);
+    let mut parent: Vec<Option<usize>> = vec![None];
+    let mut best = vec![C::default()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        if cost > best[idx] {
+            // stale entry, a cheaper way to this state was found after pushing it
+            continue;
+        }
+
+        if goal(&states[idx]) {
+            let mut path = vec![states[idx].clone()];
+            let mut cur = idx;
+            while let Some(p) = parent[cur] {
+                path.push(states[p].clone());
+                cur = p;
+            }
+            path.reverse();
+            return Some(Path { cost, states: path });
+        }
+
+        for (next, step) in successors(&states[idx]) {
+            let next_cost = cost + step;
+            let next_idx = match index.entry(next) {
+                Entry::Vacant(e) => {
+                    states.push(e.key().clone());
+                    parent.push(Some(idx));
+                    best.push(next_cost);
+                    *e.insert(states.len() - 1)
+                }
+                Entry::Occupied(e) => {
+                    let i = *e.get();
+                    if next_cost >= best[i] {
+                        continue;
+                    }
+                    parent[i] = Some(idx);
+                    best[i] = next_cost;
+                    i
+                }
+            };
+            heap.push(Reverse((
+                next_cost + heuristic(&states[next_idx]),
+                next_cost,
+                next_idx,
+            )));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dijkstra() {
+        // 0 -> 1 (4), 0 -> 2 (1), 2 -> 1 (2), 1 -> 3 (1), 2 -> 3 (7)
+        let edges: HashMap<u8, Vec<(u8, u32)>> = HashMap::from([
+            (0, vec![(1, 4), (2, 1)]),
+            (1, vec![(3, 1)]),
+            (2, vec![(1, 2), (3, 7)]),
+        ]);
+        let path = dijkstra(
+            0u8,
+            |s| edges.get(s).cloned().unwrap_or_default(),
+            |&s| s == 3,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.states, vec![0, 2, 1, 3]);
+
+        assert_eq!(
+            dijkstra(
+                0u8,
+                |s| edges.get(s).cloned().unwrap_or_default(),
+                |&s| s == 9
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_graph() {
+        let mut graph = Graph::new();
+        graph.add_edge('a', 'b', 2);
+        graph.add_edge('b', 'c', 3);
+        graph.add_edge('a', 'c', 4);
+        graph.add_edge('c', 'd', 1);
+        assert_eq!(graph.nodes().count(), 4);
+        assert_eq!(graph.successors(&'d'), vec![]);
+
+        let path = dijkstra('a', |n| graph.successors(n), |&n| n == 'd').unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(graph.longest_path(&'a', &'d'), Some(6));
+        assert_eq!(graph.longest_path(&'d', &'a'), None);
+    }
+
+    #[test]
+    fn test_astar_number_line() {
+        // reach 10 from 1 by +1 (cost 1) or *2 (cost 1)
+        let path = astar(
+            1i64,
+            |&n| {
+                [(n + 1, 1u32), (n * 2, 1u32)]
+                    .into_iter()
+                    .filter(|&(m, _)| m <= 10)
+            },
+            |&n| n == 10,
+            |&n| if n == 10 { 0 } else { 1 },
+        )
+        .unwrap();
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.states, vec![1, 2, 4, 5, 10]);
+    }
+}
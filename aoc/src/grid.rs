@@ -1,10 +1,35 @@
 use std::ops::{Index, IndexMut};
 
-use crate::point::Point;
-
-#[derive(Default, Clone, PartialEq)]
-pub struct Grid {
-    vec: Vec<Vec<u8>>,
+use crate::point::{self, Point};
+
+mod bits;
+mod expand;
+mod image;
+mod junction;
+mod pattern;
+mod prefix;
+mod ray;
+mod record;
+mod region;
+mod render;
+mod search;
+mod sparse;
+mod wrapping;
+pub use bits::BitGrid;
+pub use expand::Expansion;
+pub use image::{write_gif, Palette, Rgb};
+pub use prefix::SummedArea;
+pub use ray::Ray;
+pub use record::Recorder;
+pub use region::Region;
+pub use render::{Color, Overlay, Render};
+pub use search::Bfs;
+pub use sparse::SparseGrid;
+pub use wrapping::{Tiled, WrappingGrid};
+
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T = u8> {
+    vec: Vec<Vec<T>>,
     pub width: i32,
     pub height: i32,
 }
@@ -24,55 +49,276 @@ impl Grid {
         let width = vec.get(0).unwrap_or(&vec![]).len() as i32;
         Grid { vec, height, width }
     }
+}
+
+impl<T: Clone> Grid<T> {
+    /// creates a width*height grid with every cell set to `fill`, negative sizes become 0
+    pub fn new(width: i32, height: i32, fill: T) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Grid {
+            vec: vec![vec![fill; width as usize]; height as usize],
+            width,
+            height,
+        }
+    }
 
-    pub fn columns(&self) -> Vec<Vec<u8>> {
+    pub fn columns(&self) -> Vec<Vec<T>> {
         let mut cols = vec![Vec::with_capacity(self.height as usize); self.width as usize];
 
         for y in 0..self.height as usize {
             for x in 0..self.width as usize {
-                cols[x].push(self.vec[y][x]);
+                cols[x].push(self.vec[y][x].clone());
             }
         }
 
         cols
     }
 
-    pub fn rows(&self) -> Vec<Vec<u8>> {
+    pub fn rows(&self) -> Vec<Vec<T>> {
         self.vec.clone()
     }
 
-    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
-        self.vec.get(y).and_then(|row| row.get(x)).copied()
+    /// inserts a row of `width` cells holding `fill` before row `at`, panics if `at > height`
+    pub fn insert_row(&mut self, at: usize, fill: T) {
+        self.vec.insert(at, vec![fill; self.width.max(0) as usize]);
+        self.height += 1;
     }
 
-    pub fn get_point(&self, p: Point) -> Option<u8> {
-        if p.x < 0 || p.y < 0 {
-            return None;
+    /// inserts a column holding `fill` before column `at` of every row long enough for it,
+    /// panics if `at > width`
+    pub fn insert_col(&mut self, at: usize, fill: T) {
+        assert!(
+            at <= self.width.max(0) as usize,
+            "column {} is past the width {}",
+            at,
+            self.width
+        );
+        for row in &mut self.vec {
+            if at <= row.len() {
+                row.insert(at, fill.clone());
+            }
         }
+        self.width += 1;
+    }
+
+    /// removes and returns row `at`, panics if `at >= height`
+    pub fn remove_row(&mut self, at: usize) -> Vec<T> {
+        let row = self.vec.remove(at);
+        self.height -= 1;
+        row
+    }
 
-        let (xu, yu) = (p.x as usize, p.y as usize);
-        self.vec.get(yu).and_then(|row| row.get(xu)).copied()
+    /// removes and returns column `at`, rows too short to have it are left alone. Panics if
+    /// `at >= width`
+    pub fn remove_col(&mut self, at: usize) -> Vec<T> {
+        assert!(
+            at < self.width.max(0) as usize,
+            "column {} is outside the width {}",
+            at,
+            self.width
+        );
+        let col = self
+            .vec
+            .iter_mut()
+            .filter(|row| at < row.len())
+            .map(|row| row.remove(at))
+            .collect();
+        self.width -= 1;
+        col
+    }
+
+    /// surrounds the grid with a border `n` cells thick holding `fill`, which shifts every point
+    /// by (n, n)
+    pub fn pad(&mut self, n: usize, fill: T) {
+        for row in &mut self.vec {
+            let mut padded = vec![fill.clone(); n];
+            padded.append(row);
+            padded.resize(padded.len() + n, fill.clone());
+            *row = padded;
+        }
+        self.width += 2 * n as i32;
+        for _ in 0..n {
+            self.vec.insert(0, vec![fill.clone(); self.width as usize]);
+            self.vec.push(vec![fill.clone(); self.width as usize]);
+        }
+        self.height += 2 * n as i32;
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn get(&self, x: usize, y: usize) -> Option<T> {
+        self.vec.get(y).and_then(|row| row.get(x)).copied()
     }
 
+    pub fn get_point(&self, p: Point) -> Option<T> {
+        self.get_ref(p).copied()
+    }
+}
+
+impl<T: Clone + Default> Grid<T> {
     pub fn transpose(&mut self) {
         let old_height = self.vec.len();
         let old_width = self.vec.iter().map(|r| r.len()).max().unwrap_or(0);
-        let mut new_vec = vec![vec![0u8; old_height]; old_width];
+        let mut new_vec = vec![vec![T::default(); old_height]; old_width];
 
         for i in 0..old_height {
             let row = &self.vec[i];
             for j in 0..old_width {
-                new_vec[j][i] = *row.get(j).unwrap_or(&0);
+                new_vec[j][i] = row.get(j).cloned().unwrap_or_default();
             }
         }
 
         self.vec = new_vec;
         std::mem::swap(&mut self.width, &mut self.height);
     }
+
+    /// mirrors the grid along its vertical axis, so the first column becomes the last one. Rows
+    /// shorter than the longest row are padded with T::default before mirroring
+    pub fn flip_horizontal(&mut self) {
+        let width = self.vec.iter().map(|r| r.len()).max().unwrap_or(0);
+        for row in &mut self.vec {
+            row.resize(width, T::default());
+            row.reverse();
+        }
+        self.width = width as i32;
+    }
+
+    /// mirrors the grid along its horizontal axis, so the first row becomes the last one
+    pub fn flip_vertical(&mut self) {
+        self.vec.reverse();
+    }
+
+    /// rotates the grid by 90 degrees clockwise
+    pub fn rotate_right(&mut self) {
+        self.transpose();
+        self.flip_horizontal();
+    }
+
+    /// rotates the grid by 90 degrees counter clockwise
+    pub fn rotate_left(&mut self) {
+        self.transpose();
+        self.flip_vertical();
+    }
+
+    /// rotates the grid by 180 degrees
+    pub fn rotate_180(&mut self) {
+        self.flip_horizontal();
+        self.flip_vertical();
+    }
+
+    /// all 8 rotations and mirrorings of the grid, starting with the grid itself, followed by its
+    /// clockwise rotations and then the same four for the horizontally flipped grid. Symmetric
+    /// grids yield duplicates
+    pub fn orientations(&self) -> Vec<Grid<T>> {
+        let mut all = Vec::with_capacity(8);
+        let mut cur = self.clone();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                all.push(cur.clone());
+                cur.rotate_right();
+            }
+            cur.flip_horizontal();
+        }
+        all
+    }
 }
 
-impl Index<Point> for Grid {
-    type Output = u8;
+impl<T: Clone + Default + Ord> Grid<T> {
+    /// the lexicographically smallest (compared row by row) of all 8 orientations, two grids are
+    /// rotations or mirrorings of each other exactly if their canonical forms are equal
+    pub fn canonical(&self) -> Grid<T> {
+        self.orientations()
+            .into_iter()
+            .min_by(|a, b| a.vec.cmp(&b.vec))
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Grid<T> {
+    /// iterates over all cells in reading order, yielding their position and a reference to
+    /// their value
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.vec.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, c)| (Point::new(x as i32, y as i32), c))
+        })
+    }
+
+    /// same as Grid::iter, but yields mutable references
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.vec.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, c)| (Point::new(x as i32, y as i32), c))
+        })
+    }
+
+    /// reference to the value at `p`, None if `p` is out of bounds
+    pub fn get_ref(&self, p: Point) -> Option<&T> {
+        let (x, y) = <(usize, usize)>::try_from(p).ok()?;
+        self.vec.get(y)?.get(x)
+    }
+
+    /// reports whether `p` addresses an existing cell
+    pub fn contains(&self, p: Point) -> bool {
+        self.get_ref(p).is_some()
+    }
+
+    /// the up to four orthogonal neighbors of `p` that are inside the grid
+    pub fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        point::CARDINAL
+            .iter()
+            .map(move |&dir| p + dir)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// creates a grid of the same shape, with each cell computed by `f`
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Point, &T) -> U,
+    {
+        let vec = self
+            .vec
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, c)| f(Point::new(x as i32, y as i32), c))
+                    .collect()
+            })
+            .collect();
+        Grid {
+            vec,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// all positions whose value matches `pred`, in reading order
+    pub fn positions<'a, F>(&'a self, pred: F) -> impl Iterator<Item = Point> + 'a
+    where
+        F: Fn(&T) -> bool + 'a,
+    {
+        self.iter().filter(move |(_, c)| pred(c)).map(|(p, _)| p)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// first position (in reading order) holding `value`, think the `S` start of a maze
+    pub fn find(&self, value: T) -> Option<Point> {
+        self.iter().find(|(_, c)| **c == value).map(|(p, _)| p)
+    }
+
+    /// number of cells holding `value`
+    pub fn count(&self, value: T) -> usize {
+        self.iter().filter(|(_, c)| **c == value).count()
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
 
     #[inline]
     fn index(&self, index: Point) -> &Self::Output {
@@ -80,35 +326,35 @@ impl Index<Point> for Grid {
     }
 }
 
-impl IndexMut<Point> for Grid {
-    fn index_mut(&mut self, index: Point) -> &mut u8 {
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, index: Point) -> &mut T {
         &mut self.vec[index.y as usize][index.x as usize]
     }
 }
 
-impl Index<usize> for Grid {
-    type Output = [u8];
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
 
     fn index(&self, row: usize) -> &Self::Output {
         &self.vec[row]
     }
 }
 
-impl IndexMut<usize> for Grid {
+impl<T> IndexMut<usize> for Grid<T> {
     fn index_mut(&mut self, row: usize) -> &mut Self::Output {
         &mut self.vec[row]
     }
 }
 
-impl Index<i32> for Grid {
-    type Output = [u8];
+impl<T> Index<i32> for Grid<T> {
+    type Output = [T];
 
     fn index(&self, row: i32) -> &Self::Output {
         &self.vec[row as usize]
     }
 }
 
-impl IndexMut<i32> for Grid {
+impl<T> IndexMut<i32> for Grid<T> {
     fn index_mut(&mut self, row: i32) -> &mut Self::Output {
         &mut self.vec[row as usize]
     }
@@ -137,7 +383,7 @@ mod tests {
 
     #[test]
     fn test_empty_grid() {
-        let grid = Grid::default();
+        let grid: Grid = Grid::default();
         assert_eq!(grid.width, 0);
         assert_eq!(grid.height, 0);
         assert_eq!(grid.rows().len(), 0);
@@ -257,6 +503,159 @@ mod tests {
         assert_eq!(grid.vec, expected);
     }
 
+    #[test]
+    fn test_rotate_wide() {
+        let mut grid = Grid::from_vec(&["123".to_string(), "456".to_string()]);
+
+        grid.rotate_right();
+        assert_eq!(grid.width, 2);
+        assert_eq!(grid.height, 3);
+        let expected = vec![vec![b'4', b'1'], vec![b'5', b'2'], vec![b'6', b'3']];
+        assert_eq!(grid.vec, expected);
+
+        grid.rotate_left();
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 2);
+        assert_eq!(grid.vec, vec![b"123".to_vec(), b"456".to_vec()]);
+
+        grid.rotate_180();
+        assert_eq!(grid.vec, vec![b"654".to_vec(), b"321".to_vec()]);
+    }
+
+    #[test]
+    fn test_rotate_tall() {
+        let mut grid = Grid::from_vec(&["12".to_string(), "34".to_string(), "56".to_string()]);
+
+        grid.rotate_left();
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 2);
+        let expected = vec![vec![b'2', b'4', b'6'], vec![b'1', b'3', b'5']];
+        assert_eq!(grid.vec, expected);
+    }
+
+    #[test]
+    fn test_flip() {
+        let mut grid = Grid::from_vec(&["123".to_string(), "456".to_string()]);
+
+        grid.flip_horizontal();
+        assert_eq!(grid.vec, vec![b"321".to_vec(), b"654".to_vec()]);
+
+        grid.flip_vertical();
+        assert_eq!(grid.vec, vec![b"654".to_vec(), b"321".to_vec()]);
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 2);
+    }
+
+    #[test]
+    fn test_rotate_jagged() {
+        let mut grid = Grid::from_vec(&["12".to_string(), "345".to_string(), "6".to_string()]);
+
+        grid.rotate_right();
+        // rotation fills missing entries with 0, just like transpose
+        let expected = vec![
+            vec![b'6', b'3', b'1'],
+            vec![0, b'4', b'2'],
+            vec![0, b'5', 0],
+        ];
+        assert_eq!(grid.vec, expected);
+
+        let mut grid = Grid::from_vec(&["12".to_string(), "345".to_string()]);
+        grid.flip_horizontal();
+        assert_eq!(grid.vec, vec![vec![0, b'2', b'1'], vec![b'5', b'4', b'3']]);
+        assert_eq!(grid.width, 3);
+    }
+
+    #[test]
+    fn test_orientations_and_canonical() {
+        let grid = Grid::from_vec(&["ab".to_string(), "cd".to_string()]);
+        let orientations = grid.orientations();
+        assert_eq!(orientations.len(), 8);
+        assert_eq!(orientations[0], grid);
+        for (i, a) in orientations.iter().enumerate() {
+            for b in &orientations[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+
+        let canonical = grid.canonical();
+        assert_eq!(canonical.vec, vec![b"ab".to_vec(), b"cd".to_vec()]);
+        for o in &orientations {
+            assert_eq!(o.canonical(), canonical);
+        }
+
+        let other = Grid::from_vec(&["ab".to_string(), "dc".to_string()]);
+        assert_ne!(other.canonical(), canonical);
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut grid = Grid::from_vec(&["12".to_string(), "34".to_string()]);
+
+        grid.insert_row(1, b'.');
+        assert_eq!(grid.height, 3);
+        assert_eq!(
+            grid.vec,
+            vec![b"12".to_vec(), b"..".to_vec(), b"34".to_vec()]
+        );
+
+        grid.insert_col(2, b'|');
+        assert_eq!(grid.width, 3);
+        assert_eq!(
+            grid.vec,
+            vec![b"12|".to_vec(), b"..|".to_vec(), b"34|".to_vec()]
+        );
+
+        assert_eq!(grid.remove_col(0), b"1.3".to_vec());
+        assert_eq!(grid.remove_row(1), b".|".to_vec());
+        assert_eq!(grid.width, 2);
+        assert_eq!(grid.height, 2);
+        assert_eq!(grid.vec, vec![b"2|".to_vec(), b"4|".to_vec()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_col_past_width() {
+        let mut grid = Grid::parse("12\n34");
+        grid.insert_col(5, b'.');
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_col_past_width() {
+        let mut grid = Grid::parse("12\n34");
+        grid.remove_col(2);
+    }
+
+    #[test]
+    fn test_new_negative() {
+        let grid = Grid::new(-1, 2, b'.');
+        assert_eq!((grid.width, grid.height), (0, 2));
+        assert_eq!(grid.columns(), Vec::<Vec<u8>>::new());
+    }
+
+    #[test]
+    fn test_remove_row_past_height() {
+        // not #[should_panic] like the tests above, the grid has to be inspected after the panic
+        // to check that height was left alone
+        let mut grid = Grid::parse("12\n34");
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| grid.remove_row(2)));
+        assert!(result.is_err());
+        assert_eq!(grid.height, 2);
+    }
+
+    #[test]
+    fn test_pad() {
+        let mut grid = Grid::from_vec(&["ab".to_string()]);
+        grid.pad(1, b'#');
+        assert_eq!(grid.width, 4);
+        assert_eq!(grid.height, 3);
+        assert_eq!(
+            grid.vec,
+            vec![b"####".to_vec(), b"#ab#".to_vec(), b"####".to_vec()]
+        );
+        assert_eq!(grid[Point::new(1, 1)], b'a');
+    }
+
     #[test]
     fn test_indexing() {
         let mut grid = Grid::from_vec(&vec!["12".to_string(), "34".to_string()]);
@@ -277,4 +676,47 @@ mod tests {
         assert_eq!(grid.width, 2);
         assert_eq!(grid.vec, vec![vec![b'1', b'2'], vec![b'3', b'4']]);
     }
+
+    #[test]
+    fn test_iter() {
+        let mut grid = Grid::parse("S.#\n.#.");
+        let cells = grid.iter().map(|(p, &c)| (p, c)).collect::<Vec<_>>();
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[0], (Point::new(0, 0), b'S'));
+        assert_eq!(cells[5], (Point::new(2, 1), b'.'));
+
+        for (p, c) in grid.iter_mut() {
+            if p.x == p.y {
+                *c = b'x';
+            }
+        }
+        assert_eq!(grid.rows(), vec![b"x.#".to_vec(), b".x.".to_vec()]);
+    }
+
+    #[test]
+    fn test_find_count_positions() {
+        let grid = Grid::parse("..@\n@S@\n...");
+        assert_eq!(grid.find(b'S'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(b'E'), None);
+        assert_eq!(grid.count(b'@'), 3);
+        assert_eq!(grid.count(b'E'), 0);
+        assert_eq!(
+            grid.positions(|&c| c == b'@').collect::<Vec<_>>(),
+            vec![Point::new(2, 0), Point::new(0, 1), Point::new(2, 1)]
+        );
+        // the predicate may borrow locals
+        let target = b'S';
+        assert_eq!(grid.positions(|c| *c == target).count(), 1);
+    }
+
+    #[test]
+    fn test_new_generic() {
+        let mut grid: Grid<Option<u32>> = Grid::new(3, 2, None);
+        assert_eq!(grid.width, 3);
+        assert_eq!(grid.height, 2);
+        grid[Point::new(2, 1)] = Some(5);
+        assert_eq!(grid.get_point(Point::new(2, 1)), Some(Some(5)));
+        assert_eq!(grid.get_point(Point::new(3, 1)), None);
+        assert_eq!(grid.count(None), 5);
+    }
 }
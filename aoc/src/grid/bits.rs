@@ -0,0 +1,256 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+use super::Grid;
+use crate::point::Point;
+
+/// A boolean grid storing one bit per cell, for visited sets and masks
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl BitGrid {
+    /// a grid with all bits cleared, negative sizes become 0
+    pub fn new(width: i32, height: i32) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        let cells = width as usize * height as usize;
+        BitGrid {
+            words: vec![0; cells.div_ceil(64)],
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x >= 0 && p.y >= 0 && p.x < self.width && p.y < self.height
+    }
+
+    fn index(&self, p: Point) -> (usize, u64) {
+        let i = p.y as usize * self.width as usize + p.x as usize;
+        (i / 64, 1 << (i % 64))
+    }
+
+    /// state of the bit at `p`, points outside the grid are unset
+    pub fn get(&self, p: Point) -> bool {
+        if !self.contains(p) {
+            return false;
+        }
+        let (word, bit) = self.index(p);
+        self.words[word] & bit != 0
+    }
+
+    /// sets the bit at `p` to `value` and returns its previous state, panics outside the grid
+    pub fn set(&mut self, p: Point, value: bool) -> bool {
+        assert!(self.contains(p), "{:?} is outside the grid", p);
+        let (word, bit) = self.index(p);
+        let previous = self.words[word] & bit != 0;
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+        previous
+    }
+
+    /// sets the bit at `p`, returns true if it was not set before, like HashSet::insert
+    pub fn insert(&mut self, p: Point) -> bool {
+        !self.set(p, true)
+    }
+
+    /// clears the bit at `p`, returns true if it was set before
+    pub fn remove(&mut self, p: Point) -> bool {
+        self.set(p, false)
+    }
+
+    /// clears all bits
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// number of set bits
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// iterates over the set points in reading order
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        let width = self.width as usize;
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                let cell = i * 64 + bit;
+                Some(Point::new((cell % width) as i32, (cell / width) as i32))
+            })
+        })
+    }
+
+    fn combine<F: Fn(u64, u64) -> u64>(&mut self, other: &BitGrid, op: F) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grid dimensions differ"
+        );
+        for (a, &b) in self.words.iter_mut().zip(&other.words) {
+            *a = op(*a, b);
+        }
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    /// union, both grids must have the same dimensions
+    fn bitor_assign(&mut self, rhs: &BitGrid) {
+        self.combine(rhs, |a, b| a | b);
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    /// intersection, both grids must have the same dimensions
+    fn bitand_assign(&mut self, rhs: &BitGrid) {
+        self.combine(rhs, |a, b| a & b);
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, rhs: &BitGrid) -> BitGrid {
+        let mut out = self.clone();
+        out |= rhs;
+        out
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, rhs: &BitGrid) -> BitGrid {
+        let mut out = self.clone();
+        out &= rhs;
+        out
+    }
+}
+
+impl FromIterator<Point> for BitGrid {
+    /// a grid just large enough to hold all points, which must not be negative
+    fn from_iter<I: IntoIterator<Item = Point>>(iter: I) -> Self {
+        let points = iter.into_iter().collect::<Vec<_>>();
+        let width = points.iter().map(|p| p.x + 1).max().unwrap_or(0);
+        let height = points.iter().map(|p| p.y + 1).max().unwrap_or(0);
+        let mut bits = BitGrid::new(width, height);
+        for p in points {
+            bits.insert(p);
+        }
+        bits
+    }
+}
+
+impl<T> Grid<T> {
+    /// a BitGrid covering every cell with the bits set where `pred` holds. Jagged grids get a
+    /// mask as wide as their longest row
+    pub fn mask<F>(&self, pred: F) -> BitGrid
+    where
+        F: Fn(&T) -> bool,
+    {
+        let width = self.vec.iter().map(Vec::len).max().unwrap_or(0);
+        let mut bits = BitGrid::new(width as i32, self.vec.len() as i32);
+        for (p, value) in self.iter() {
+            if pred(value) {
+                bits.insert(p);
+            }
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_get() {
+        let mut bits = BitGrid::new(10, 10);
+        assert!(bits.is_empty());
+        assert!(bits.insert(Point::new(9, 9)));
+        assert!(!bits.insert(Point::new(9, 9)));
+        assert!(bits.insert(Point::new(3, 6)));
+        assert!(bits.get(Point::new(3, 6)));
+        assert!(!bits.get(Point::new(6, 3)));
+        assert!(!bits.get(Point::new(-1, 0)));
+        assert_eq!(bits.count(), 2);
+        assert!(bits.remove(Point::new(3, 6)));
+        assert!(!bits.remove(Point::new(3, 6)));
+        assert_eq!(bits.iter().collect::<Vec<_>>(), vec![Point::new(9, 9)]);
+        bits.clear();
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn test_new_negative() {
+        let bits = BitGrid::new(-3, 4);
+        assert_eq!((bits.width, bits.height), (0, 4));
+        assert!(!bits.contains(Point::new(0, 0)));
+        assert_eq!(bits.iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_outside() {
+        BitGrid::new(3, 3).insert(Point::new(3, 0));
+    }
+
+    #[test]
+    fn test_mask_and_ops() {
+        let grid = Grid::parse("#.#\n.##\n..#");
+        let walls = grid.mask(|&c| c == b'#');
+        assert_eq!(walls.count(), 5);
+        assert_eq!(
+            walls.iter().collect::<Vec<_>>(),
+            grid.positions(|&c| c == b'#').collect::<Vec<_>>()
+        );
+
+        let right = [Point::new(2, 0), Point::new(2, 1), Point::new(2, 2)]
+            .into_iter()
+            .collect::<BitGrid>();
+        assert_eq!((right.width, right.height), (3, 3));
+        assert_eq!((&walls & &right).count(), 3);
+        assert_eq!((&walls | &right), walls);
+
+        let mut visited = BitGrid::new(3, 3);
+        visited.insert(Point::new(0, 2));
+        visited |= &walls;
+        assert_eq!(visited.count(), 6);
+        visited &= &grid.mask(|&c| c == b'.');
+        assert_eq!(visited.iter().collect::<Vec<_>>(), vec![Point::new(0, 2)]);
+    }
+
+    #[test]
+    fn test_mask_jagged() {
+        let grid = Grid::from_vec(&["ab".to_string(), "cde".to_string()]);
+        let bits = grid.mask(|_| true);
+        assert_eq!((bits.width, bits.height), (3, 2));
+        assert_eq!(bits.count(), 5);
+        assert!(!bits.get(Point::new(2, 0)));
+        assert!(bits.get(Point::new(2, 1)));
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let mut bits = BitGrid::new(100, 3);
+        let points = [Point::new(63, 0), Point::new(64, 0), Point::new(27, 1)];
+        for p in points {
+            bits.insert(p);
+        }
+        assert_eq!(bits.iter().collect::<Vec<_>>(), points);
+    }
+}
@@ -0,0 +1,112 @@
+use super::Grid;
+use crate::point::Point;
+
+/// Maps points of a grid into the coordinates they would have if every empty row and column was
+/// repeated `factor` times, without materializing the expanded grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expansion {
+    rows: Vec<i64>,
+    cols: Vec<i64>,
+}
+
+impl Expansion {
+    /// expanded position of `p`, points outside the grid keep extending the last offset
+    pub fn map(&self, p: Point) -> Point<i64> {
+        Point::new(
+            Expansion::axis(&self.cols, p.x),
+            Expansion::axis(&self.rows, p.y),
+        )
+    }
+
+    fn axis(mapped: &[i64], i: i32) -> i64 {
+        let Some(&last) = mapped.last() else {
+            return i as i64;
+        };
+        if i < 0 {
+            return mapped[0] + i as i64;
+        }
+        match mapped.get(i as usize) {
+            Some(&m) => m,
+            None => last + i as i64 - (mapped.len() as i64 - 1),
+        }
+    }
+
+    /// position of each of the `len` indexes after the ones before them grew
+    fn offsets<F: Fn(usize) -> bool>(len: usize, factor: i64, empty: F) -> Vec<i64> {
+        let mut mapped = Vec::with_capacity(len);
+        let mut cur = 0;
+        for i in 0..len {
+            mapped.push(cur);
+            cur += if empty(i) { factor } else { 1 };
+        }
+        mapped
+    }
+}
+
+impl<T> Grid<T> {
+    /// computes where each row and column ends up if rows and columns whose cells all satisfy
+    /// `empty` grow to `factor` copies of themselves, for instance factor 2 doubles them
+    pub fn expansion<F>(&self, empty: F, factor: i64) -> Expansion
+    where
+        F: Fn(&T) -> bool,
+    {
+        let width = self.vec.iter().map(Vec::len).max().unwrap_or(0);
+        let rows = Expansion::offsets(self.vec.len(), factor, |y| self.vec[y].iter().all(&empty));
+        let cols = Expansion::offsets(width, factor, |x| {
+            self.vec.iter().all(|row| row.get(x).is_none_or(&empty))
+        });
+        Expansion { rows, cols }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIVERSE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    fn distances(factor: i64) -> i64 {
+        let grid = Grid::parse(UNIVERSE);
+        let expansion = grid.expansion(|&c| c == b'.', factor);
+        let galaxies = grid
+            .positions(|&c| c == b'#')
+            .map(|p| expansion.map(p))
+            .collect::<Vec<_>>();
+        let mut sum = 0;
+        for (i, a) in galaxies.iter().enumerate() {
+            for b in &galaxies[i + 1..] {
+                sum += a.manhattan(*b);
+            }
+        }
+        sum
+    }
+
+    #[test]
+    fn test_expansion() {
+        assert_eq!(distances(2), 374);
+        assert_eq!(distances(10), 1030);
+        assert_eq!(distances(100), 8410);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = Grid::parse("#..\n...\n..#");
+        let expansion = grid.expansion(|&c| c == b'.', 1_000_000);
+        assert_eq!(expansion.map(Point::new(0, 0)), Point::new(0, 0));
+        assert_eq!(
+            expansion.map(Point::new(2, 2)),
+            Point::new(1_000_001, 1_000_001)
+        );
+        assert_eq!(expansion.map(Point::new(3, -1)), Point::new(1_000_002, -1));
+    }
+}
@@ -0,0 +1,219 @@
+use std::{borrow::Borrow, collections::HashMap, io};
+
+use super::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// Maps cell values to colors for image export, values without entry use `fallback`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Palette {
+    pub colors: HashMap<u8, Rgb>,
+    pub fallback: Rgb,
+}
+
+impl Palette {
+    pub fn new(fallback: Rgb) -> Self {
+        Palette {
+            colors: HashMap::new(),
+            fallback,
+        }
+    }
+
+    pub fn get(&self, value: u8) -> Rgb {
+        *self.colors.get(&value).unwrap_or(&self.fallback)
+    }
+}
+
+impl Default for Palette {
+    /// walls (`#`) white, everything else black
+    fn default() -> Self {
+        let mut palette = Palette::new([0, 0, 0]);
+        palette.colors.insert(b'#', [255, 255, 255]);
+        palette
+    }
+}
+
+impl Grid {
+    /// pixel dimensions of the grid with each cell drawn as a `scale` x `scale` square
+    fn image_size(&self, scale: u32) -> (u32, u32) {
+        let width = self.vec.iter().map(Vec::len).max().unwrap_or(0) as u32;
+        (width * scale, self.vec.len() as u32 * scale)
+    }
+
+    /// one byte per pixel, computed by `pixel` from the cells value, out of grid pixels are 0
+    fn pixels<F: Fn(Option<u8>) -> u8>(&self, scale: u32, pixel: F) -> Vec<u8> {
+        let (width, height) = self.image_size(scale);
+        let mut out = Vec::with_capacity((width * height) as usize);
+        for y in 0..height as usize {
+            let row = &self.vec[y / scale as usize];
+            for x in 0..width as usize {
+                out.push(pixel(row.get(x / scale as usize).copied()));
+            }
+        }
+        out
+    }
+
+    fn rgb(&self, palette: &Palette, scale: u32) -> Vec<u8> {
+        let (width, height) = self.image_size(scale);
+        let mut out = Vec::with_capacity((width * height * 3) as usize);
+        for y in 0..height as usize {
+            let row = &self.vec[y / scale as usize];
+            for x in 0..width as usize {
+                let color = row
+                    .get(x / scale as usize)
+                    .map_or(palette.fallback, |&c| palette.get(c));
+                out.extend_from_slice(&color);
+            }
+        }
+        out
+    }
+
+    /// writes the grid as a binary PPM (P6) image
+    pub fn write_ppm<W: io::Write>(
+        &self,
+        out: &mut W,
+        palette: &Palette,
+        scale: u32,
+    ) -> io::Result<()> {
+        let (width, height) = self.image_size(scale);
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+        out.write_all(&self.rgb(palette, scale))
+    }
+
+    /// writes the grid as an RGB PNG image
+    pub fn write_png<W: io::Write>(
+        &self,
+        out: &mut W,
+        palette: &Palette,
+        scale: u32,
+    ) -> io::Result<()> {
+        let (width, height) = self.image_size(scale);
+        let mut encoder = png::Encoder::new(out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut w| w.write_image_data(&self.rgb(palette, scale)))
+            .map_err(io::Error::other)
+    }
+}
+
+/// assembles `frames` into a looping animated GIF, showing each frame for `delay` hundredths of
+/// a second. The image is as large as the largest frame, palettes are limited to 255 colors
+pub fn write_gif<W, I>(
+    out: &mut W,
+    frames: I,
+    palette: &Palette,
+    scale: u32,
+    delay: u16,
+) -> io::Result<()>
+where
+    W: io::Write,
+    I: IntoIterator,
+    I::Item: Borrow<Grid>,
+{
+    let frames = frames.into_iter().collect::<Vec<_>>();
+
+    // index 0 is the fallback color, each palette entry gets its own index after that
+    let mut index = [0u8; 256];
+    let mut colors = palette.fallback.to_vec();
+    let mut entries = palette.colors.iter().collect::<Vec<_>>();
+    entries.sort();
+    if entries.len() > 255 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "gif palettes hold at most 255 colors besides the fallback",
+        ));
+    }
+    for (i, (&value, color)) in entries.into_iter().enumerate() {
+        index[value as usize] = i as u8 + 1;
+        colors.extend_from_slice(color);
+    }
+
+    let (width, height) = frames
+        .iter()
+        .map(|f| f.borrow().image_size(scale))
+        .fold((0, 0), |(w, h), (fw, fh)| (w.max(fw), h.max(fh)));
+    let too_large = |_| io::Error::new(io::ErrorKind::InvalidInput, "gif dimensions exceed u16");
+    let (width, height) = (
+        u16::try_from(width).map_err(too_large)?,
+        u16::try_from(height).map_err(too_large)?,
+    );
+
+    let mut encoder = gif::Encoder::new(out, width, height, &colors).map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for grid in &frames {
+        let grid = grid.borrow();
+        let (w, h) = grid.image_size(scale);
+        let pixels = grid.pixels(scale, |c| c.map_or(0, |c| index[c as usize]));
+        let mut frame = gif::Frame::from_indexed_pixels(w as u16, h as u16, pixels, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ppm() {
+        let grid = Grid::parse("#.\n.#");
+        let mut out = vec![];
+        grid.write_ppm(&mut out, &Palette::default(), 2).unwrap();
+
+        let header = b"P6\n4 4\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        let pixels = &out[header.len()..];
+        assert_eq!(pixels.len(), 4 * 4 * 3);
+        let white = [255, 255, 255];
+        let black = [0, 0, 0];
+        assert_eq!(pixels[0..3], white);
+        assert_eq!(pixels[3..6], white);
+        assert_eq!(pixels[6..9], black);
+        // third row, first pixel belongs to the second grid row
+        assert_eq!(pixels[2 * 12..2 * 12 + 3], black);
+        assert_eq!(pixels[2 * 12 + 6..2 * 12 + 9], white);
+    }
+
+    #[test]
+    fn test_png() {
+        let grid = Grid::parse("#..\n.#.");
+        let mut out = vec![];
+        grid.write_png(&mut out, &Palette::default(), 3).unwrap();
+
+        let decoder = png::Decoder::new(out.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (9, 6));
+        assert_eq!(buf[0..3], [255, 255, 255]);
+        assert_eq!(buf[9..12], [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_gif() {
+        let mut palette = Palette::new([0, 0, 0]);
+        palette.colors.insert(b'#', [255, 0, 0]);
+        palette.colors.insert(b'o', [0, 0, 255]);
+        let frames = [Grid::parse("#.\n.."), Grid::parse("o.\n.#")];
+        let mut out = vec![];
+        write_gif(&mut out, &frames, &palette, 1, 10).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(out.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (2, 2));
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 10);
+        assert_eq!(first.buffer[0..4], [255, 0, 0, 255]);
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(second.buffer[0..4], [0, 0, 255, 255]);
+        assert_eq!(second.buffer[12..16], [255, 0, 0, 255]);
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+}
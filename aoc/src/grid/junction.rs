@@ -0,0 +1,125 @@
+use super::Grid;
+use crate::{
+    graph::Graph,
+    point::{self, Point},
+};
+
+impl<T> Grid<T> {
+    /// collapses the corridors of a maze into a weighted graph between its junctions.
+    ///
+    /// Nodes are the `passable` cells with a number of passable neighbors other than two
+    /// (junctions and dead ends) plus `keep`, usually start and goal. `can_step(from, to, dir)`
+    /// additionally restricts moving from the value `from` onto its neighbor `to` in direction
+    /// `dir`, which models one way slopes; corridors that can not be walked produce no edge.
+    /// Edges are weighted with the corridor length in steps
+    pub fn junction_graph<P, S>(&self, passable: P, can_step: S, keep: &[Point]) -> Graph<Point>
+    where
+        P: Fn(&T) -> bool,
+        S: Fn(&T, &T, Point) -> bool,
+    {
+        let open = |p: Point| self.get_ref(p).is_some_and(&passable);
+        let step = |from: Point, dir: Point| {
+            open(from + dir) && can_step(&self[from], &self[from + dir], dir)
+        };
+        let is_node = |p: Point| {
+            keep.contains(&p) || point::CARDINAL.iter().filter(|&&d| open(p + d)).count() != 2
+        };
+
+        let mut graph = Graph::new();
+        for (node, _) in self.iter() {
+            if !open(node) || !is_node(node) {
+                continue;
+            }
+            graph.edges.entry(node).or_default();
+
+            for &dir in &point::CARDINAL {
+                if !step(node, dir) {
+                    continue;
+                }
+                let (mut prev, mut cur, mut length) = (node, node + dir, 1);
+                // follow the corridor until the next node, giving up at a dead end
+                while !is_node(cur) {
+                    let Some(next) = point::CARDINAL
+                        .iter()
+                        .filter(|&&d| cur + d != prev && step(cur, d))
+                        .map(|&d| cur + d)
+                        .next()
+                    else {
+                        break;
+                    };
+                    (prev, cur, length) = (cur, next, length + 1);
+                }
+                if is_node(cur) && cur != node {
+                    graph.add_edge(node, cur, length);
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HIKE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    fn slopes(from: &u8, to: &u8, dir: Point) -> bool {
+        let allowed = |c: &u8| match c {
+            b'>' => dir == point::RIGHT,
+            b'<' => dir == point::LEFT,
+            b'v' => dir == point::DOWN,
+            b'^' => dir == point::UP,
+            _ => true,
+        };
+        allowed(from) && allowed(to)
+    }
+
+    #[test]
+    fn test_junction_graph() {
+        let grid = Grid::parse(HIKE);
+        let (start, goal) = (Point::new(1, 0), Point::new(21, 22));
+
+        let graph = grid.junction_graph(|&c| c != b'#', slopes, &[start, goal]);
+        assert_eq!(graph.longest_path(&start, &goal), Some(94));
+
+        let graph = grid.junction_graph(|&c| c != b'#', |_, _, _| true, &[start, goal]);
+        assert_eq!(graph.longest_path(&start, &goal), Some(154));
+        // 7 junctions plus start and goal
+        assert_eq!(graph.nodes().count(), 9);
+        assert_eq!(graph.successors(&start), vec![(Point::new(3, 5), 15)]);
+    }
+
+    #[test]
+    fn test_shortest_path_matches_bfs() {
+        let grid = Grid::parse(HIKE);
+        let (start, goal) = (Point::new(1, 0), Point::new(21, 22));
+        let graph = grid.junction_graph(|&c| c != b'#', |_, _, _| true, &[start, goal]);
+        let path = crate::graph::dijkstra(start, |n| graph.successors(n), |&n| n == goal).unwrap();
+        let bfs = grid.bfs(start, |_, &to| to != b'#');
+        assert_eq!(Some(path.cost), bfs.distance(goal));
+    }
+}
@@ -0,0 +1,120 @@
+use super::Grid;
+use crate::point::{self, Point};
+
+impl<T: PartialEq> Grid<T> {
+    /// finds every occurrence of `word` written in any of the eight directions of
+    /// point::DIAGONAL, returns the position of the first letter and the direction the word is
+    /// read in. Palindromes are found once per reading direction
+    pub fn find_word(&self, word: &[T]) -> Vec<(Point, Point)> {
+        let Some(first) = word.first() else {
+            return vec![];
+        };
+
+        self.iter()
+            .filter(|(_, c)| *c == first)
+            .flat_map(|(start, _)| {
+                point::DIAGONAL.iter().filter_map(move |&dir| {
+                    word.iter()
+                        .enumerate()
+                        .all(|(i, w)| self.get_ref(start + dir * i as i32) == Some(w))
+                        .then_some((start, dir))
+                })
+            })
+            .collect()
+    }
+
+    /// top left positions at which `pattern` matches the grid, cells of the pattern holding
+    /// `wildcard` match anything inside the grid
+    pub fn find_pattern(&self, pattern: &Grid<T>, wildcard: &T) -> Vec<Point> {
+        self.iter()
+            .map(|(p, _)| p)
+            .filter(|&origin| {
+                pattern.iter().all(|(p, c)| match self.get_ref(origin + p) {
+                    Some(cell) => c == wildcard || c == cell,
+                    None => false,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<T: PartialEq + Clone + Default> Grid<T> {
+    /// same as Grid::find_pattern, but tries every distinct rotation and mirroring of `pattern`,
+    /// each match yields the top left position and the matching orientation
+    pub fn find_pattern_oriented(&self, pattern: &Grid<T>, wildcard: &T) -> Vec<(Point, Grid<T>)> {
+        let mut orientations: Vec<Grid<T>> = vec![];
+        for o in pattern.orientations() {
+            if !orientations.contains(&o) {
+                orientations.push(o);
+            }
+        }
+
+        orientations
+            .into_iter()
+            .flat_map(|o| {
+                self.find_pattern(&o, wildcard)
+                    .into_iter()
+                    .map(move |p| (p, o.clone()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEARCH: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_find_word() {
+        let grid = Grid::parse(SEARCH);
+        let found = grid.find_word(b"XMAS");
+        assert_eq!(found.len(), 18);
+        assert!(found.contains(&(Point::new(5, 0), point::RIGHT)));
+        assert!(found.contains(&(Point::new(4, 1), point::LEFT)));
+
+        let grid = Grid::parse("ABA");
+        assert_eq!(grid.find_word(b"ABA").len(), 2);
+        assert_eq!(grid.find_word(b"").len(), 0);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let grid = Grid::parse("abab\nbaba\nabab");
+        let pattern = Grid::parse("a.\n.a");
+        assert_eq!(
+            grid.find_pattern(&pattern, &b'.'),
+            vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1)]
+        );
+        assert!(grid
+            .find_pattern(&Grid::parse("b."), &b'.')
+            .contains(&Point::new(1, 0)));
+        assert!(!grid
+            .find_pattern(&Grid::parse("b."), &b'.')
+            .contains(&Point::new(3, 1)));
+    }
+
+    #[test]
+    fn test_find_pattern_oriented() {
+        let grid = Grid::parse(SEARCH);
+        let pattern = Grid::parse("M.S\n.A.\nM.S");
+        assert_eq!(grid.find_pattern_oriented(&pattern, &b'.').len(), 9);
+
+        let grid = Grid::parse("ab.\n...\nba.");
+        let pattern = Grid::parse("ab");
+        let found = grid.find_pattern_oriented(&pattern, &b'.');
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&(Point::new(0, 2), Grid::parse("ba"))));
+    }
+}
@@ -0,0 +1,128 @@
+use super::Grid;
+use crate::point::Point;
+
+/// Summed-area table (2D prefix sums) for constant time rectangle sums
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummedArea {
+    /// sum of all cells above and left of each point, with an extra zero row and column
+    sums: Vec<i64>,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl SummedArea {
+    fn at(&self, x: i32, y: i32) -> i64 {
+        self.sums[y as usize * (self.width as usize + 1) + x as usize]
+    }
+
+    /// sum of the rectangle with the corners `a` and `b`, both inclusive and in any order.
+    /// Parts outside the grid count as 0
+    pub fn sum(&self, a: Point, b: Point) -> i64 {
+        let x0 = a.x.min(b.x).clamp(0, self.width);
+        let y0 = a.y.min(b.y).clamp(0, self.height);
+        let x1 = (a.x.max(b.x) + 1).clamp(0, self.width);
+        let y1 = (a.y.max(b.y) + 1).clamp(0, self.height);
+        if x0 >= x1 || y0 >= y1 {
+            return 0;
+        }
+        self.at(x1, y1) - self.at(x0, y1) - self.at(x1, y0) + self.at(x0, y0)
+    }
+
+    /// sums of all `size` x `size` squares fully inside the grid, keyed by their top left corner.
+    /// Yields nothing if `size` is not positive
+    pub fn squares(&self, size: i32) -> impl Iterator<Item = (Point, i64)> + '_ {
+        let (w, h) = if size > 0 {
+            (self.width - size + 1, self.height - size + 1)
+        } else {
+            (0, 0)
+        };
+        (0..h.max(0)).flat_map(move |y| {
+            (0..w.max(0)).map(move |x| {
+                let p = Point::new(x, y);
+                (p, self.sum(p, p + Point::new(size - 1, size - 1)))
+            })
+        })
+    }
+
+    /// the `size` x `size` square with the largest sum as its top left corner and sum, ties go to
+    /// the first one in reading order
+    pub fn max_square(&self, size: i32) -> Option<(Point, i64)> {
+        self.squares(size)
+            .fold(None, |best: Option<(Point, i64)>, (p, sum)| match best {
+                Some((_, b)) if b >= sum => best,
+                _ => Some((p, sum)),
+            })
+    }
+}
+
+impl<T> Grid<T> {
+    /// builds a summed-area table over the values `f` maps each cell to, missing cells of jagged
+    /// rows count as 0
+    pub fn summed_area<F>(&self, f: F) -> SummedArea
+    where
+        F: Fn(Point, &T) -> i64,
+    {
+        let width = self.vec.iter().map(Vec::len).max().unwrap_or(0);
+        let stride = width + 1;
+        let mut sums = vec![0; stride * (self.vec.len() + 1)];
+        for (y, row) in self.vec.iter().enumerate() {
+            let mut line = 0;
+            for x in 0..width {
+                if let Some(value) = row.get(x) {
+                    line += f(Point::new(x as i32, y as i32), value);
+                }
+                sums[(y + 1) * stride + x + 1] = sums[y * stride + x + 1] + line;
+            }
+        }
+        SummedArea {
+            sums,
+            width: width as i32,
+            height: self.vec.len() as i32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum() {
+        let grid = Grid::parse("123\n456\n789");
+        let table = grid.summed_area(|_, &c| (c - b'0') as i64);
+        assert_eq!(table.sum(Point::new(0, 0), Point::new(2, 2)), 45);
+        assert_eq!(table.sum(Point::new(2, 2), Point::new(1, 1)), 28);
+        assert_eq!(table.sum(Point::new(1, 0), Point::new(1, 2)), 15);
+        assert_eq!(table.sum(Point::new(-5, 2), Point::new(9, 9)), 24);
+        assert_eq!(table.sum(Point::new(3, 0), Point::new(4, 4)), 0);
+        assert_eq!(table.max_square(2), Some((Point::new(1, 1), 28)));
+        assert_eq!(table.squares(3).count(), 1);
+        assert_eq!(table.max_square(4), None);
+        assert_eq!(table.squares(0).count(), 0);
+        assert_eq!(table.max_square(0), None);
+        assert_eq!(table.max_square(-2), None);
+    }
+
+    #[test]
+    fn test_jagged() {
+        let table = Grid::parse("##\n#\n###").summed_area(|_, &c| (c == b'#') as i64);
+        assert_eq!(table.width, 3);
+        assert_eq!(table.sum(Point::new(0, 0), Point::new(2, 2)), 6);
+        assert_eq!(table.sum(Point::new(1, 1), Point::new(2, 1)), 0);
+    }
+
+    fn power(serial: i64) -> SummedArea {
+        Grid::new(300, 300, 0u8).summed_area(|p, _| {
+            let (x, y) = (p.x as i64 + 1, p.y as i64 + 1);
+            let rack = x + 10;
+            ((rack * y + serial) * rack / 100) % 10 - 5
+        })
+    }
+
+    #[test]
+    fn test_power_grid() {
+        let table = power(18);
+        assert_eq!(table.max_square(3), Some((Point::new(32, 44), 29)));
+        assert_eq!(power(42).max_square(3), Some((Point::new(20, 60), 30)));
+    }
+}
@@ -0,0 +1,194 @@
+use std::iter::FusedIterator;
+
+use super::Grid;
+use crate::point::Point;
+
+/// Iterator over the cells from a start point in a fixed direction, excluding the start. Ends
+/// at the first step that leaves the grid after entering it
+#[derive(Clone)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Point,
+    dir: Point,
+    done: bool,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.pos += self.dir;
+        let value = self.grid.get_ref(self.pos);
+        self.done = value.is_none();
+        Some((self.pos, value?))
+    }
+}
+
+impl<T> FusedIterator for Ray<'_, T> {}
+
+/// range of steps k for which `c + k * d` lies in [0, len), None if there are none
+fn steps(c: i32, d: i32, len: i32) -> Option<(i32, i32)> {
+    if d == 0 {
+        return (0..len).contains(&c).then_some((i32::MIN, i32::MAX));
+    }
+    // mirror negative directions so d > 0 and the range becomes [lo, hi]
+    let (c, d, lo, hi) = if d > 0 {
+        (c, d, 0, len - 1)
+    } else {
+        (-c, -d, 1 - len, 0)
+    };
+    let first = -(c - lo).div_euclid(d);
+    let last = (hi - c).div_euclid(d);
+    (first <= last).then_some((first, last))
+}
+
+impl<T> Grid<T> {
+    /// walks from `start` in steps of `dir` through the grid, `start` itself is not yielded. A
+    /// start outside the grid skips ahead to where the ray enters its bounding box. A zero `dir`
+    /// yields nothing
+    pub fn ray(&self, start: Point, dir: Point) -> Ray<'_, T> {
+        let width = self.vec.iter().map(Vec::len).max().unwrap_or(0) as i32;
+        let entry = steps(start.x, dir.x, width)
+            .zip(steps(start.y, dir.y, self.vec.len() as i32))
+            .map(|((a, b), (c, d))| (a.max(c).max(1), b.min(d)))
+            .filter(|(first, last)| first <= last);
+        match entry {
+            Some((first, _)) if dir != Point::default() => Ray {
+                grid: self,
+                pos: start + dir * (first - 1),
+                dir,
+                done: false,
+            },
+            _ => Ray {
+                grid: self,
+                pos: start,
+                dir,
+                done: true,
+            },
+        }
+    }
+
+    /// first point along the ray from `start` in `dir` whose value satisfies `pred`
+    pub fn first_hit<F>(&self, start: Point, dir: Point, pred: F) -> Option<Point>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.ray(start, dir)
+            .find(|(_, value)| pred(value))
+            .map(|(p, _)| p)
+    }
+
+    /// checks if `to` can be seen from `from`, i.e. no lattice point strictly between them on
+    /// the line connecting them holds a value that `blocks`. Both points must be in the grid
+    pub fn visible<F>(&self, from: Point, to: Point, blocks: F) -> bool
+    where
+        F: Fn(&T) -> bool,
+    {
+        if !self.contains(from) || !self.contains(to) {
+            return false;
+        }
+        let step = (to - from).reduced();
+        self.ray(from, step)
+            .take_while(|&(p, _)| p != to)
+            .all(|(_, value)| !blocks(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point;
+
+    const TREES: &str = "30373\n25512\n65332\n33549\n35390";
+
+    #[test]
+    fn test_ray() {
+        let grid = Grid::parse(TREES);
+        let row = grid
+            .ray(Point::new(1, 1), point::RIGHT)
+            .map(|(_, &c)| c)
+            .collect::<Vec<_>>();
+        assert_eq!(row, b"512");
+        let diagonal = grid
+            .ray(Point::new(0, 0), Point::new(2, 2))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagonal,
+            vec![(Point::new(2, 2), &b'3'), (Point::new(4, 4), &b'0')]
+        );
+        assert_eq!(grid.ray(Point::new(2, 2), point::ORIGIN).count(), 0);
+        // rays may start outside the grid
+        assert_eq!(grid.ray(Point::new(-1, 0), point::RIGHT).count(), 5);
+        let entered = grid.ray(Point::new(-2, 9), Point::new(1, -2));
+        assert_eq!(
+            entered.map(|(p, _)| p).collect::<Vec<_>>(),
+            vec![Point::new(1, 3), Point::new(2, 1)]
+        );
+        assert_eq!(grid.ray(Point::new(-5, 0), point::RIGHT).count(), 5);
+        assert_eq!(grid.ray(Point::new(-5, 0), point::LEFT).count(), 0);
+        assert_eq!(grid.ray(Point::new(2, 7), point::RIGHT).count(), 0);
+        assert_eq!(grid.ray(Point::new(4, 4), point::RIGHT).count(), 0);
+    }
+
+    #[test]
+    fn test_ray_fused() {
+        let grid = Grid::parse("ab\ncd");
+        let mut ray = grid.ray(Point::new(0, 0), point::RIGHT);
+        assert_eq!(ray.next(), Some((Point::new(1, 0), &b'b')));
+        assert_eq!(ray.next(), None);
+        assert_eq!(ray.next(), None);
+        // a jagged gap ends the ray for good, even if later steps would be inside again
+        let grid = Grid::from_vec(&["abc".to_string(), "d".to_string(), "ghi".to_string()]);
+        let mut ray = grid.ray(Point::new(2, -1), point::DOWN);
+        assert_eq!(ray.next(), Some((Point::new(2, 0), &b'c')));
+        assert_eq!(ray.next(), None);
+        assert_eq!(ray.next(), None);
+    }
+
+    #[test]
+    fn test_tree_visibility() {
+        let grid = Grid::parse(TREES);
+        let visible = grid
+            .iter()
+            .filter(|&(p, h)| {
+                point::CARDINAL
+                    .iter()
+                    .any(|&d| grid.first_hit(p, d, |t| t >= h).is_none())
+            })
+            .count();
+        assert_eq!(visible, 21);
+
+        let scenic = |p: Point| -> usize {
+            point::CARDINAL
+                .iter()
+                .map(|&d| match grid.first_hit(p, d, |t| *t >= grid[p]) {
+                    Some(hit) => hit.manhattan(p) as usize,
+                    None => grid.ray(p, d).count(),
+                })
+                .product()
+        };
+        assert_eq!(scenic(Point::new(2, 3)), 8);
+        assert_eq!(grid.iter().map(|(p, _)| scenic(p)).max(), Some(8));
+    }
+
+    #[test]
+    fn test_visible() {
+        let grid = Grid::parse(".#..#\n.....\n#####\n....#\n...##");
+        let asteroids = grid.positions(|&c| c == b'#').collect::<Vec<_>>();
+        let count = |from: Point| {
+            asteroids
+                .iter()
+                .filter(|&&to| to != from && grid.visible(from, to, |&c| c == b'#'))
+                .count()
+        };
+        assert_eq!(count(Point::new(3, 4)), 8);
+        assert_eq!(count(Point::new(4, 2)), 5);
+        assert_eq!(asteroids.iter().map(|&a| count(a)).max(), Some(8));
+        assert!(!grid.visible(Point::new(0, 2), Point::new(9, 2), |_| false));
+        assert_eq!(Point::new(4, -6).reduced(), Point::new(2, -3));
+        assert_eq!(Point::new(0, -7).reduced(), point::UP);
+    }
+}
@@ -0,0 +1,399 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    path::Path,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use super::{Grid, Render};
+use crate::point::Point;
+
+#[derive(Clone)]
+enum Frame {
+    Full(Grid),
+    Diff(Vec<(Point, u8)>),
+}
+
+/// Records Grid snapshots of a simulation, frames are stored as the cells that changed since the
+/// previous frame, only the first frame and frames with changed dimensions are kept whole
+#[derive(Clone, Default)]
+pub struct Recorder {
+    frames: Vec<Frame>,
+    last: Grid,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder::default()
+    }
+
+    /// appends `grid` as the next frame
+    pub fn record(&mut self, grid: &Grid) {
+        let same_shape = !self.frames.is_empty()
+            && self.last.width == grid.width
+            && self.last.height == grid.height
+            && self
+                .last
+                .vec
+                .iter()
+                .map(Vec::len)
+                .eq(grid.vec.iter().map(Vec::len));
+        if same_shape {
+            let diff = grid
+                .iter()
+                .filter(|&(p, &c)| self.last[p] != c)
+                .map(|(p, &c)| (p, c))
+                .collect::<Vec<_>>();
+            for &(p, c) in &diff {
+                self.last[p] = c;
+            }
+            self.frames.push(Frame::Diff(diff));
+        } else {
+            self.last = grid.clone();
+            self.frames.push(Frame::Full(grid.clone()));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// reconstructs the `i`th recorded grid
+    pub fn frame(&self, i: usize) -> Option<Grid> {
+        if i >= self.frames.len() {
+            return None;
+        }
+        let key = (0..=i)
+            .rev()
+            .find(|&k| matches!(self.frames[k], Frame::Full(_)))?;
+        let Frame::Full(grid) = &self.frames[key] else {
+            unreachable!("key is a full frame");
+        };
+        let mut grid = grid.clone();
+        for frame in &self.frames[key + 1..=i] {
+            if let Frame::Diff(diff) = frame {
+                for &(p, c) in diff {
+                    grid[p] = c;
+                }
+            }
+        }
+        Some(grid)
+    }
+
+    /// all recorded grids in order, cheaper than calling Recorder::frame for each index
+    pub fn grids(&self) -> impl Iterator<Item = Grid> + '_ {
+        let mut cur = Grid::default();
+        self.frames.iter().map(move |frame| {
+            match frame {
+                Frame::Full(grid) => cur = grid.clone(),
+                Frame::Diff(diff) => {
+                    for &(p, c) in diff {
+                        cur[p] = c;
+                    }
+                }
+            }
+            cur.clone()
+        })
+    }
+
+    /// writes the recording to `path`, full frames are stored as their rows, diffs as one
+    /// `x y value` line per changed cell. Grids must not contain line breaks
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut out = io::BufWriter::new(fs::File::create(path)?);
+        for frame in &self.frames {
+            match frame {
+                Frame::Full(grid) => {
+                    writeln!(out, "full {}", grid.vec.len())?;
+                    for row in &grid.vec {
+                        if row.iter().any(|&c| c == b'\n' || c == b'\r') {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "grid contains a line break",
+                            ));
+                        }
+                        out.write_all(row)?;
+                        writeln!(out)?;
+                    }
+                }
+                Frame::Diff(diff) => {
+                    writeln!(out, "diff {}", diff.len())?;
+                    for (p, c) in diff {
+                        writeln!(out, "{} {} {}", p.x, p.y, c)?;
+                    }
+                }
+            }
+        }
+        out.flush()
+    }
+
+    /// reads a recording written by Recorder::save
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        // rows of full frames are raw bytes which need not be valid UTF-8
+        let content = fs::read(path)?;
+        let content = content.strip_suffix(b"\n").unwrap_or(&content);
+        let mut lines = content
+            .split(|&c| c == b'\n')
+            .filter(|_| !content.is_empty());
+        let text = |line: &[u8]| {
+            std::str::from_utf8(line)
+                .map(|l| l.trim_end_matches('\r').to_string())
+                .map_err(|_| invalid("frame header or diff entry is not UTF-8"))
+        };
+        let mut recorder = Recorder::new();
+
+        while let Some(header) = lines.next() {
+            let header = text(header)?;
+            let (kind, count) = header
+                .split_once(' ')
+                .ok_or_else(|| invalid("missing frame header"))?;
+            let count = count
+                .parse::<usize>()
+                .map_err(|_| invalid("bad frame length"))?;
+            let body = lines.by_ref().take(count).collect::<Vec<_>>();
+            if body.len() != count {
+                return Err(invalid("truncated frame"));
+            }
+
+            match kind {
+                "full" => {
+                    let vec = body.iter().map(|r| r.to_vec()).collect::<Vec<_>>();
+                    let grid = Grid {
+                        width: vec.first().map_or(0, |r| r.len()) as i32,
+                        height: vec.len() as i32,
+                        vec,
+                    };
+                    recorder.last = grid.clone();
+                    recorder.frames.push(Frame::Full(grid));
+                }
+                "diff" => {
+                    let mut diff = Vec::with_capacity(count);
+                    for line in body {
+                        let line = text(line)?;
+                        let mut fields = line.split(' ');
+                        let (Some(x), Some(y), Some(c), None) =
+                            (fields.next(), fields.next(), fields.next(), fields.next())
+                        else {
+                            return Err(invalid("bad diff entry"));
+                        };
+                        let (Ok(x), Ok(y), Ok(c)) =
+                            (x.parse::<i32>(), y.parse::<i32>(), c.parse::<u8>())
+                        else {
+                            return Err(invalid("bad diff entry"));
+                        };
+                        let p = Point::new(x, y);
+                        if !recorder.last.contains(p) {
+                            return Err(invalid("diff entry out of bounds"));
+                        }
+                        recorder.last[p] = c;
+                        diff.push((p, c));
+                    }
+                    recorder.frames.push(Frame::Diff(diff));
+                }
+                _ => return Err(invalid("unknown frame kind")),
+            }
+        }
+
+        Ok(recorder)
+    }
+
+    /// interactive replay in the terminal, reads commands from stdin, see Recorder::replay_with
+    pub fn replay(&self, delay: Duration) -> io::Result<()> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        let clear = io::stdout().is_terminal();
+        self.replay_with(rx, &mut io::stdout().lock(), delay, clear)
+    }
+
+    /// replays the recording to `out`, one command per received line: an empty line or `n`
+    /// steps forward, `b` steps back, `p` toggles playing with `delay` between frames, a number
+    /// jumps to that frame and `q` quits. While playing any input pauses. `clear` clears the
+    /// screen before each frame
+    pub fn replay_with<W: Write>(
+        &self,
+        input: Receiver<String>,
+        out: &mut W,
+        delay: Duration,
+        clear: bool,
+    ) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+
+        let mut cur = 0;
+        let mut grid = self.frame(0).unwrap_or_default();
+        let mut playing = false;
+        loop {
+            let render = Render::new(&grid);
+            if clear {
+                write!(out, "\x1b[2J\x1b[H")?;
+            }
+            writeln!(out, "frame {}/{}", cur, self.frames.len() - 1)?;
+            write!(out, "{}", render)?;
+            writeln!(
+                out,
+                "[enter/n] next [b] back [p] play/pause [<n>] jump [q] quit"
+            )?;
+            out.flush()?;
+
+            let cmd = if playing {
+                match input.recv_timeout(delay) {
+                    Ok(_) => {
+                        playing = false;
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Timeout) => String::from("n"),
+                    Err(RecvTimeoutError::Disconnected) => String::from("n"),
+                }
+            } else {
+                match input.recv() {
+                    Ok(cmd) => cmd,
+                    Err(_) => return Ok(()),
+                }
+            };
+
+            match cmd.trim() {
+                "" | "n" if cur + 1 < self.frames.len() => {
+                    cur += 1;
+                    if let Frame::Diff(diff) = &self.frames[cur] {
+                        for &(p, c) in diff {
+                            grid[p] = c;
+                        }
+                    } else {
+                        grid = self.frame(cur).unwrap_or_default();
+                    }
+                }
+                "" | "n" => playing = false,
+                "b" => {
+                    cur = cur.saturating_sub(1);
+                    grid = self.frame(cur).unwrap_or_default();
+                }
+                "p" => playing = !playing,
+                "q" => return Ok(()),
+                n => {
+                    if let Ok(i) = n.parse::<usize>() {
+                        cur = i.min(self.frames.len() - 1);
+                        grid = self.frame(cur).unwrap_or_default();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording() -> Recorder {
+        let mut recorder = Recorder::new();
+        let mut grid = Grid::parse("...\n...");
+        recorder.record(&grid);
+        grid[Point::new(0, 0)] = b'#';
+        recorder.record(&grid);
+        grid[Point::new(2, 1)] = b'#';
+        recorder.record(&grid);
+        recorder.record(&Grid::parse("ab"));
+        recorder
+    }
+
+    #[test]
+    fn test_frames() {
+        let recorder = recording();
+        assert_eq!(recorder.len(), 4);
+        assert_eq!(recorder.frame(0), Some(Grid::parse("...\n...")));
+        assert_eq!(recorder.frame(2), Some(Grid::parse("#..\n..#")));
+        assert_eq!(recorder.frame(3), Some(Grid::parse("ab")));
+        assert_eq!(recorder.frame(4), None);
+        assert_eq!(
+            recorder.grids().collect::<Vec<_>>(),
+            (0..4).filter_map(|i| recorder.frame(i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_save_load() {
+        let recorder = recording();
+        let path = std::env::temp_dir().join(format!("aoc-record-{}.txt", std::process::id()));
+        recorder.save(&path).unwrap();
+        let loaded = Recorder::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), recorder.len());
+        for i in 0..recorder.len() {
+            assert_eq!(loaded.frame(i), recorder.frame(i));
+        }
+    }
+
+    #[test]
+    fn test_save_load_high_bytes() {
+        let mut recorder = Recorder::new();
+        let mut grid = Grid::parse("ab\ncd");
+        grid[Point::new(1, 0)] = 0xff;
+        grid[Point::new(0, 1)] = 0x80;
+        recorder.record(&grid);
+        grid[Point::new(1, 1)] = 0xe9;
+        recorder.record(&grid);
+
+        let path = std::env::temp_dir().join(format!("aoc-record-hi-{}.txt", std::process::id()));
+        recorder.save(&path).unwrap();
+        let loaded = Recorder::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.frame(0), recorder.frame(0));
+        assert_eq!(loaded.frame(1), Some(grid));
+    }
+
+    #[test]
+    fn test_load_diff_out_of_range() {
+        let path = std::env::temp_dir().join(format!("aoc-record-bad-{}.txt", std::process::id()));
+        fs::write(&path, "full 2\nab\ncd\ndiff 1\n1 0 300\n").unwrap();
+        let err = Recorder::load(&path).err().unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "bad diff entry");
+    }
+
+    #[test]
+    fn test_replay() {
+        let recorder = recording();
+        let (tx, rx) = mpsc::channel();
+        for cmd in ["n", "", "b", "3", "q"] {
+            tx.send(cmd.to_string()).unwrap();
+        }
+        let mut out = vec![];
+        recorder
+            .replay_with(rx, &mut out, Duration::from_millis(1), false)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let shown = out
+            .lines()
+            .filter(|l| l.starts_with("frame"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shown,
+            vec![
+                "frame 0/3",
+                "frame 1/3",
+                "frame 2/3",
+                "frame 1/3",
+                "frame 3/3"
+            ]
+        );
+        assert!(out.contains("#..\n..#\n"));
+    }
+}
@@ -0,0 +1,107 @@
+use super::Grid;
+use crate::point::{self, Point, PointSet};
+
+/// A set of cells with its measures: `perimeter` counts unit edges between the region and
+/// everything else, `sides` counts maximal straight fence segments, holes included
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub cells: Vec<Point>,
+    pub area: usize,
+    pub perimeter: usize,
+    pub sides: usize,
+}
+
+impl Region {
+    /// measures an arbitrary set of points, duplicates are ignored
+    pub fn from_points<I: IntoIterator<Item = Point>>(points: I) -> Self {
+        let set = points.into_iter().collect::<PointSet>();
+        let inside = |p: Point| set.contains(&p);
+
+        let mut perimeter = 0;
+        // a polygon has as many sides as corners, so count the corners of every cell
+        let mut sides = 0;
+        for &p in &set {
+            perimeter += point::CARDINAL.iter().filter(|&&d| !inside(p + d)).count();
+            for i in 0..4 {
+                let a = point::CARDINAL[i];
+                let b = point::CARDINAL[(i + 1) % 4];
+                let (in_a, in_b, in_diag) = (inside(p + a), inside(p + b), inside(p + a + b));
+                // convex corner, or concave corner where the diagonal cell is missing
+                if (!in_a && !in_b) || (in_a && in_b && !in_diag) {
+                    sides += 1;
+                }
+            }
+        }
+
+        let mut cells = set.into_iter().collect::<Vec<_>>();
+        cells.sort();
+        Region {
+            area: cells.len(),
+            cells,
+            perimeter,
+            sides,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// splits the grid into regions of orthogonally connected cells, see Grid::components, and
+    /// measures each of them. Regions are ordered by their first cell in reading order
+    pub fn regions<F>(&self, connected: F) -> Vec<Region>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let (labels, count) = self.components(connected);
+        let mut members = vec![vec![]; count];
+        for (p, &label) in labels.iter() {
+            members[label].push(p);
+        }
+        members.into_iter().map(Region::from_points).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(garden: &str) -> (usize, usize) {
+        Grid::parse(garden)
+            .regions(|a, b| a == b)
+            .iter()
+            .fold((0, 0), |(perimeter, sides), r| {
+                (perimeter + r.area * r.perimeter, sides + r.area * r.sides)
+            })
+    }
+
+    #[test]
+    fn test_regions() {
+        let regions = Grid::parse("AAAA\nBBCD\nBBCC\nEEEC").regions(|a, b| a == b);
+        let measures = regions
+            .iter()
+            .map(|r| (r.area, r.perimeter, r.sides))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            measures,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+        assert_eq!(regions[3].cells, vec![Point::new(3, 1)]);
+    }
+
+    #[test]
+    fn test_holes() {
+        assert_eq!(prices("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"), (772, 436));
+
+        let ring = Region::from_points(Grid::parse("###\n#.#\n###").positions(|&c| c == b'#'));
+        assert_eq!((ring.area, ring.perimeter, ring.sides), (8, 16, 8));
+    }
+
+    #[test]
+    fn test_sides() {
+        assert_eq!(prices("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE").1, 236);
+        // the B regions touch diagonally, which must not merge their sides
+        assert_eq!(
+            prices("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA").1,
+            368
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::{self, IsTerminal, Write},
+};
+
+use super::Grid;
+use crate::point::Point;
+
+/// The 8 standard ANSI terminal colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+/// A set of points drawn on top of the grid, for instance a path or the cells a beam passed
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    pub points: HashSet<Point>,
+    /// background color of the overlaid cells
+    pub color: Color,
+    /// replaces the cells value if set, the only visible part of an overlay in plain text mode
+    pub glyph: Option<u8>,
+}
+
+impl Overlay {
+    pub fn new<I: IntoIterator<Item = Point>>(points: I, color: Color) -> Self {
+        Overlay {
+            points: points.into_iter().collect(),
+            color,
+            glyph: None,
+        }
+    }
+}
+
+/// Terminal renderer for Grid, displays the grid with colors, overlays, rulers and cropping.
+/// Configure it via the public fields, then print or format it
+pub struct Render<'a> {
+    grid: &'a Grid,
+    /// foreground color per cell value, cells without entry are printed uncolored
+    pub palette: HashMap<u8, Color>,
+    /// drawn in order, later overlays win
+    pub overlays: Vec<Overlay>,
+    /// prints column numbers above and row numbers left of the grid
+    pub rulers: bool,
+    /// only renders cells at most `radius` cells (chebyshev distance) away from `center`
+    pub window: Option<(Point, i32)>,
+    /// emit ANSI escape codes, defaults to whether stdout is a terminal
+    pub color: bool,
+}
+
+impl<'a> Render<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        Render {
+            grid,
+            palette: HashMap::new(),
+            overlays: vec![],
+            rulers: false,
+            window: None,
+            color: io::stdout().is_terminal(),
+        }
+    }
+
+    /// writes the rendered grid to stdout
+    pub fn print(&self) -> io::Result<()> {
+        write!(io::stdout().lock(), "{}", self)
+    }
+
+    /// inclusive top left and bottom right corner of the rendered area
+    fn area(&self) -> (Point, Point) {
+        let (mut min, mut max) = (
+            Point::new(0, 0),
+            Point::new(self.grid.width - 1, self.grid.height - 1),
+        );
+        if let Some((center, radius)) = self.window {
+            min = Point::new(
+                (center.x - radius).max(min.x),
+                (center.y - radius).max(min.y),
+            );
+            max = Point::new(
+                (center.x + radius).min(max.x),
+                (center.y + radius).min(max.y),
+            );
+        }
+        (min, max)
+    }
+}
+
+impl fmt::Display for Render<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, max) = self.area();
+        let label_width = max.y.max(0).to_string().len();
+
+        if self.rulers {
+            let digits = max.x.max(0).to_string().len() as u32;
+            for d in (0..digits).rev() {
+                write!(f, "{:>w$} ", "", w = label_width)?;
+                for x in min.x..=max.x {
+                    write!(f, "{}", x / 10i32.pow(d) % 10)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        for y in min.y..=max.y {
+            if self.rulers {
+                write!(f, "{:>w$} ", y, w = label_width)?;
+            }
+            for x in min.x..=max.x {
+                let p = Point::new(x, y);
+                let Some(mut cell) = self.grid.get_point(p) else {
+                    write!(f, " ")?;
+                    continue;
+                };
+                let overlay = self.overlays.iter().rev().find(|o| o.points.contains(&p));
+                if let Some(glyph) = overlay.and_then(|o| o.glyph) {
+                    cell = glyph;
+                }
+
+                if !self.color {
+                    write!(f, "{}", cell as char)?;
+                    continue;
+                }
+                if let Some(o) = overlay {
+                    write!(f, "\x1b[4{}m", o.color.code())?;
+                }
+                if let Some(c) = self.palette.get(&cell) {
+                    write!(f, "\x1b[3{}m", c.code())?;
+                }
+                write!(f, "{}", cell as char)?;
+                if overlay.is_some() || self.palette.contains_key(&cell) {
+                    write!(f, "\x1b[0m")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain() {
+        let grid = Grid::parse("S..\n.#.\n..E");
+        let mut render = Render::new(&grid);
+        render.color = false;
+        let mut path = Overlay::new([Point::new(1, 0), Point::new(2, 0)], Color::Blue);
+        path.glyph = Some(b'o');
+        render.overlays.push(path);
+        assert_eq!(render.to_string(), "Soo\n.#.\n..E\n");
+    }
+
+    #[test]
+    fn test_color() {
+        let grid = Grid::parse("#.\n.#");
+        let mut render = Render::new(&grid);
+        render.color = true;
+        render.palette.insert(b'#', Color::Red);
+        render
+            .overlays
+            .push(Overlay::new([Point::new(1, 0)], Color::Green));
+        assert_eq!(
+            render.to_string(),
+            "\x1b[31m#\x1b[0m\x1b[42m.\x1b[0m\n.\x1b[31m#\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_rulers_and_window() {
+        let grid = Grid::from_vec(&(0..12).map(|_| "a".repeat(12)).collect::<Vec<_>>());
+        let mut render = Render::new(&grid);
+        render.color = false;
+        render.rulers = true;
+        render.window = Some((Point::new(10, 10), 1));
+        assert_eq!(
+            render.to_string(),
+            "   011\n   901\n 9 aaa\n10 aaa\n11 aaa\n"
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::VecDeque;
+
+use super::Grid;
+use crate::{
+    graph::{self, Path},
+    point::Point,
+};
+
+/// Result of a breadth first search over a Grid: the unweighted distance to every reached cell
+/// and the cell it was reached from, sources have a distance of 0 and no predecessor
+#[derive(Clone, PartialEq)]
+pub struct Bfs {
+    pub dist: Grid<Option<u32>>,
+    pub prev: Grid<Option<Point>>,
+}
+
+impl Bfs {
+    /// distance from the closest source to `to`, None if `to` was not reached
+    pub fn distance(&self, to: Point) -> Option<u32> {
+        self.dist.get_point(to).flatten()
+    }
+
+    /// reconstructs a shortest path from a source to `to`, both inclusive
+    pub fn path(&self, to: Point) -> Option<Vec<Point>> {
+        self.distance(to)?;
+        let mut path = vec![to];
+        let mut cur = to;
+        while let Some(prev) = self.prev[cur] {
+            path.push(prev);
+            cur = prev;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl<T> Grid<T> {
+    /// breadth first search from `start` over orthogonal neighbors, `passable(from, to)` decides
+    /// if a step from the value `from` onto the value `to` is allowed
+    pub fn bfs<F>(&self, start: Point, passable: F) -> Bfs
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        self.bfs_multi([start], passable)
+    }
+
+    /// same as Grid::bfs, but starts from all `starts` at once, so each distance is the distance
+    /// to the closest source
+    pub fn bfs_multi<I, F>(&self, starts: I, passable: F) -> Bfs
+    where
+        I: IntoIterator<Item = Point>,
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut dist = self.map(|_, _| None);
+        let mut prev = self.map(|_, _| None);
+        let mut queue = VecDeque::new();
+
+        for s in starts {
+            if self.contains(s) && dist[s].is_none() {
+                dist[s] = Some(0);
+                queue.push_back(s);
+            }
+        }
+
+        while let Some(cur) = queue.pop_front() {
+            let d = dist[cur].unwrap_or_default();
+            for n in self.neighbors(cur) {
+                if dist[n].is_some() || !passable(&self[cur], &self[n]) {
+                    continue;
+                }
+                dist[n] = Some(d + 1);
+                prev[n] = Some(cur);
+                queue.push_back(n);
+            }
+        }
+
+        Bfs { dist, prev }
+    }
+
+    /// all cells reachable from `start` (including `start`), in breadth first order
+    pub fn flood_fill<F>(&self, start: Point, passable: F) -> Vec<Point>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        if !self.contains(start) {
+            return vec![];
+        }
+
+        let mut seen = self.map(|_, _| false);
+        let mut queue = VecDeque::from([start]);
+        let mut filled = vec![];
+        seen[start] = true;
+
+        while let Some(cur) = queue.pop_front() {
+            filled.push(cur);
+            for n in self.neighbors(cur) {
+                if !seen[n] && passable(&self[cur], &self[n]) {
+                    seen[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// labels connected regions, two orthogonally adjacent cells belong to the same region if
+    /// `connected(a, b)` holds. Returns a grid of region ids, numbered 0.. in reading order of
+    /// each regions first cell, and the number of regions
+    pub fn components<F>(&self, connected: F) -> (Grid<usize>, usize)
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        let mut labels: Grid<Option<usize>> = self.map(|_, _| None);
+        let mut count = 0;
+        // one queue shared by all regions, the labels double as the seen set
+        let mut queue = VecDeque::new();
+
+        for (p, _) in self.iter() {
+            if labels[p].is_some() {
+                continue;
+            }
+            labels[p] = Some(count);
+            queue.push_back(p);
+            while let Some(cur) = queue.pop_front() {
+                for n in self.neighbors(cur) {
+                    if labels[n].is_none() && connected(&self[cur], &self[n]) {
+                        labels[n] = Some(count);
+                        queue.push_back(n);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        (labels.map(|_, l| l.unwrap_or_default()), count)
+    }
+
+    /// cheapest orthogonal walk from `start` to `goal` via A* with a manhattan heuristic,
+    /// `cost(from, to)` returns the price of stepping from the value `from` onto `to` or None if
+    /// the step is not allowed. Each step must cost at least 1 to keep the heuristic admissible
+    pub fn shortest_path<F>(&self, start: Point, goal: Point, cost: F) -> Option<Path<Point, u32>>
+    where
+        F: Fn(&T, &T) -> Option<u32>,
+    {
+        if !self.contains(start) {
+            return None;
+        }
+
+        graph::astar(
+            start,
+            |&cur| {
+                self.neighbors(cur)
+                    .filter_map(|n| cost(&self[cur], &self[n]).map(|c| (n, c)))
+                    .collect::<Vec<_>>()
+            },
+            |&p| p == goal,
+            |&p| p.manhattan(goal) as u32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::Grid;
+    use crate::point::Point;
+
+    const MAZE: &str = "\
+S.#.
+.##.
+...E";
+
+    fn open(_: &u8, to: &u8) -> bool {
+        *to != b'#'
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::parse(MAZE);
+        let start = grid.find(b'S').unwrap();
+        let end = grid.find(b'E').unwrap();
+        let bfs = grid.bfs(start, open);
+
+        assert_eq!(bfs.distance(start), Some(0));
+        assert_eq!(bfs.distance(end), Some(5));
+        assert_eq!(bfs.distance(Point::new(3, 0)), Some(7));
+        assert_eq!(bfs.distance(Point::new(2, 0)), None);
+
+        let path = bfs.path(end).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0], start);
+        assert_eq!(path[5], end);
+    }
+
+    #[test]
+    fn test_bfs_multi() {
+        let grid = Grid::parse(MAZE);
+        let bfs = grid.bfs_multi([Point::new(0, 0), Point::new(3, 0)], open);
+        assert_eq!(bfs.distance(Point::new(3, 2)), Some(2));
+        assert_eq!(bfs.distance(Point::new(1, 2)), Some(3));
+    }
+
+    #[test]
+    fn test_bfs_climb() {
+        let grid = Grid::parse("abc\nzzd\ngfe");
+        let bfs = grid.bfs(Point::new(0, 0), |&a, &b| b <= a + 1);
+        assert_eq!(bfs.distance(Point::new(0, 2)), Some(6));
+        assert_eq!(bfs.distance(Point::new(0, 1)), None);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let grid = Grid::parse("1163\n1381\n2136");
+        let path = grid
+            .shortest_path(Point::new(0, 0), Point::new(3, 2), |_, &to| {
+                Some((to - b'0') as u32)
+            })
+            .unwrap();
+        assert_eq!(path.cost, 1 + 2 + 1 + 3 + 6);
+        assert_eq!(path.states.first(), Some(&Point::new(0, 0)));
+        assert_eq!(path.states.last(), Some(&Point::new(3, 2)));
+
+        let maze = Grid::parse(MAZE);
+        let path = maze
+            .shortest_path(Point::new(0, 0), Point::new(3, 0), |_, &to| {
+                (to != b'#').then_some(1)
+            })
+            .unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.states.len(), 8);
+        assert!(maze
+            .shortest_path(Point::new(0, 0), Point::new(2, 0), |_, &to| {
+                (to != b'#').then_some(1)
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn test_flood_fill_and_components() {
+        let grid = Grid::parse("AAB\nABB\nCCB");
+        let mut filled = grid.flood_fill(Point::new(2, 0), |a, b| a == b);
+        filled.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(
+            filled,
+            vec![
+                Point::new(2, 0),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(2, 2)
+            ]
+        );
+
+        let (labels, count) = grid.components(|a, b| a == b);
+        assert_eq!(count, 3);
+        assert_eq!(
+            labels.rows(),
+            vec![vec![0, 0, 1], vec![0, 1, 1], vec![2, 2, 1]]
+        );
+        assert!(grid.flood_fill(Point::new(5, 5), |a, b| a == b).is_empty());
+    }
+
+    #[test]
+    fn test_components_checkerboard() {
+        // every cell is its own region, which must not cost a full grid pass per region
+        let grid = Grid::new(300, 300, 0u8).map(|p, _| ((p.x + p.y) % 2) as u8);
+        let (labels, count) = grid.components(|a, b| a == b);
+        assert_eq!(count, 90_000);
+        assert_eq!(labels[Point::new(299, 299)], 89_999);
+    }
+}
@@ -0,0 +1,228 @@
+use std::{collections::HashMap, fmt, ops::Index};
+
+use super::Grid;
+use crate::point::{self, Point};
+
+/// Unbounded grid, only cells that were set are stored, every other cell reads as `default`.
+/// Tracks the bounding box of all stored cells, so it can be printed and converted like a Grid
+#[derive(Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    default: T,
+    min: Point,
+    max: Point,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            min: Point::default(),
+            max: Point::default(),
+        }
+    }
+
+    /// value at `p`, or the default value if `p` was never set
+    pub fn get(&self, p: Point) -> &T {
+        self.cells.get(&p).unwrap_or(&self.default)
+    }
+
+    pub fn set(&mut self, p: Point, value: T) {
+        if self.cells.is_empty() {
+            self.min = p;
+            self.max = p;
+        } else {
+            self.min = Point::new(self.min.x.min(p.x), self.min.y.min(p.y));
+            self.max = Point::new(self.max.x.max(p.x), self.max.y.max(p.y));
+        }
+        self.cells.insert(p, value);
+    }
+
+    /// removes the value at `p`, shrinking the bounding box if `p` was on its edge
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        let removed = self.cells.remove(&p)?;
+        if p.x == self.min.x || p.y == self.min.y || p.x == self.max.x || p.y == self.max.y {
+            self.recompute_bounds();
+        }
+        Some(removed)
+    }
+
+    fn recompute_bounds(&mut self) {
+        let mut keys = self.cells.keys();
+        let Some(&first) = keys.next() else {
+            self.min = Point::default();
+            self.max = Point::default();
+            return;
+        };
+        let (min, max) = keys.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+        self.min = min;
+        self.max = max;
+    }
+
+    /// reports whether a value was explicitly set at `p`
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    /// number of stored cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// inclusive top left and bottom right corners of all stored cells, None if empty
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        (!self.cells.is_empty()).then_some((self.min, self.max))
+    }
+
+    /// width of the bounding box
+    pub fn width(&self) -> i32 {
+        self.bounds().map_or(0, |(min, max)| max.x - min.x + 1)
+    }
+
+    /// height of the bounding box
+    pub fn height(&self) -> i32 {
+        self.bounds().map_or(0, |(min, max)| max.y - min.y + 1)
+    }
+
+    /// all stored cells, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    /// the four orthogonal neighbors of `p`, there is no edge to stop at
+    pub fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> {
+        point::CARDINAL.into_iter().map(move |dir| p + dir)
+    }
+
+    /// all eight neighbors of `p`, including diagonals
+    pub fn neighbors_diagonal(&self, p: Point) -> impl Iterator<Item = Point> {
+        point::DIAGONAL.into_iter().map(move |dir| p + dir)
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// mutable reference to the value at `p`, storing the default value first if `p` was unset
+    pub fn get_mut(&mut self, p: Point) -> &mut T {
+        if !self.cells.contains_key(&p) {
+            self.set(p, self.default.clone());
+        }
+        self.cells.get_mut(&p).expect("cell was just inserted")
+    }
+
+    /// densifies the bounding box into a Grid, unset cells are filled with the default value.
+    /// Returns the grid and the position of its top left corner, which maps to Point (0, 0)
+    pub fn to_grid(&self) -> (Grid<T>, Point) {
+        let mut grid = Grid::new(self.width(), self.height(), self.default.clone());
+        for (&p, v) in &self.cells {
+            grid[p - self.min] = v.clone();
+        }
+        (grid, self.min)
+    }
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    /// stores every cell of `grid` that does not hold `default`
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self {
+        let mut sparse = SparseGrid::new(default);
+        for (p, v) in grid.iter() {
+            if *v != sparse.default {
+                sparse.set(p, v.clone());
+            }
+        }
+        sparse
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl fmt::Debug for SparseGrid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "SparseGrid {{ width: {}, height: {} }}",
+            self.width(),
+            self.height()
+        )?;
+        if self.is_empty() {
+            return Ok(());
+        }
+        for y in self.min.y..=self.max.y {
+            let line = (self.min.x..=self.max.x)
+                .map(|x| *self.get(Point::new(x, y)) as char)
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_and_bounds() {
+        let mut grid = SparseGrid::new(b'.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.width(), 0);
+        assert_eq!(grid[Point::new(100, -100)], b'.');
+
+        grid.set(Point::new(-2, 3), b'#');
+        grid.set(Point::new(4, -1), b'o');
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(4, 3))));
+        assert_eq!(grid.width(), 7);
+        assert_eq!(grid.height(), 5);
+        assert_eq!(grid[Point::new(-2, 3)], b'#');
+        assert_eq!(grid.len(), 2);
+
+        assert_eq!(grid.remove(Point::new(4, -1)), Some(b'o'));
+        assert_eq!(grid.remove(Point::new(4, -1)), None);
+        assert_eq!(grid.bounds(), Some((Point::new(-2, 3), Point::new(-2, 3))));
+    }
+
+    #[test]
+    fn test_get_mut_and_neighbors() {
+        let mut grid = SparseGrid::new(0u32);
+        *grid.get_mut(Point::new(1, 1)) += 5;
+        *grid.get_mut(Point::new(1, 1)) += 5;
+        assert_eq!(grid[Point::new(1, 1)], 10);
+
+        let around = grid.neighbors(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(around, point::CARDINAL.to_vec());
+        assert_eq!(grid.neighbors_diagonal(Point::new(0, 0)).count(), 8);
+    }
+
+    #[test]
+    fn test_grid_round_trip_and_debug() {
+        let dense = Grid::parse("..#\n#..\n...");
+        let mut sparse = SparseGrid::from_grid(&dense, b'.');
+        assert_eq!(sparse.len(), 2);
+        sparse.set(Point::new(-1, 0), b'o');
+
+        assert_eq!(
+            format!("{:?}", sparse),
+            "SparseGrid { width: 4, height: 2 }\no..#\n.#..\n"
+        );
+
+        let (grid, offset) = sparse.to_grid();
+        assert_eq!(offset, Point::new(-1, 0));
+        assert_eq!(grid.rows(), vec![b"o..#".to_vec(), b".#..".to_vec()]);
+    }
+}
@@ -0,0 +1,130 @@
+use std::ops::{Index, IndexMut};
+
+use super::Grid;
+use crate::point::{self, Point};
+
+/// Grid whose edges wrap around (a torus), walking off the right edge enters on the left one.
+/// Indexing and neighbors always resolve to coordinates inside the wrapped grid
+#[derive(Default, Clone, PartialEq)]
+pub struct WrappingGrid<T = u8> {
+    pub grid: Grid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    pub fn new(grid: Grid<T>) -> Self {
+        WrappingGrid { grid }
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// maps any point onto the cell it addresses in the wrapped grid
+    pub fn wrap(&self, p: Point) -> Point {
+        p.rem_euclid(self.grid.width, self.grid.height)
+    }
+
+    /// the four orthogonal neighbors of `p`, wrapped around the edges
+    pub fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        point::CARDINAL
+            .into_iter()
+            .map(move |dir| self.wrap(p + dir))
+    }
+}
+
+impl<T> Index<Point> for WrappingGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        &self.grid[self.wrap(index)]
+    }
+}
+
+impl<T> IndexMut<Point> for WrappingGrid<T> {
+    fn index_mut(&mut self, index: Point) -> &mut T {
+        let p = self.wrap(index);
+        &mut self.grid[p]
+    }
+}
+
+/// Read only view of a grid repeated infinitely in every direction ("the map repeats forever").
+/// Unlike WrappingGrid, points are not folded back, so each copy of a cell stays distinguishable
+/// and Tiled::tile tells which copy a point lies in
+#[derive(Clone, Copy)]
+pub struct Tiled<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T> Tiled<'a, T> {
+    pub fn get(&self, p: Point) -> &'a T {
+        &self.grid[p.rem_euclid(self.grid.width, self.grid.height)]
+    }
+
+    /// which repetition of the grid `p` is in, the original grid is tile (0, 0)
+    pub fn tile(&self, p: Point) -> Point {
+        p.div_euclid(self.grid.width, self.grid.height)
+    }
+
+    /// the four orthogonal neighbors of `p`, there is no edge to stop at
+    pub fn neighbors(&self, p: Point) -> impl Iterator<Item = Point> {
+        point::CARDINAL.into_iter().map(move |dir| p + dir)
+    }
+}
+
+impl<T> Index<Point> for Tiled<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T> Grid<T> {
+    /// infinitely repeating read only view of the grid, see Tiled
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        Tiled { grid: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrapping_index() {
+        let mut grid = WrappingGrid::new(Grid::parse("abc\ndef"));
+        assert_eq!(grid[Point::new(-1, 0)], b'c');
+        assert_eq!(grid[Point::new(3, 1)], b'd');
+        assert_eq!(grid[Point::new(-4, -3)], b'f');
+
+        grid[Point::new(5, 2)] = b'x';
+        assert_eq!(grid.grid[Point::new(2, 0)], b'x');
+
+        let mut around = grid.neighbors(Point::new(0, 0)).collect::<Vec<_>>();
+        around.sort_by_key(|p| (p.y, p.x));
+        assert_eq!(
+            around,
+            vec![
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(0, 1),
+                Point::new(0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tiled() {
+        let grid = Grid::parse("ab\ncd");
+        let tiled = grid.tiled();
+        assert_eq!(tiled[Point::new(2, 0)], b'a');
+        assert_eq!(tiled[Point::new(-1, -1)], b'd');
+        assert_eq!(tiled.tile(Point::new(-1, -1)), Point::new(-1, -1));
+        assert_eq!(tiled.tile(Point::new(5, 1)), Point::new(2, 0));
+        assert!(tiled
+            .neighbors(Point::new(0, 0))
+            .any(|p| p == Point::new(-1, 0)));
+    }
+}
@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
+};
+
+pub const ORIGIN: Hex = Hex::new(0, 0);
+pub const EAST: Hex = Hex::new(1, 0);
+pub const WEST: Hex = Hex::new(-1, 0);
+pub const NORTH_EAST: Hex = Hex::new(1, -1);
+pub const NORTH_WEST: Hex = Hex::new(0, -1);
+pub const SOUTH_EAST: Hex = Hex::new(0, 1);
+pub const SOUTH_WEST: Hex = Hex::new(-1, 1);
+/// the six neighbor directions, clockwise starting at EAST
+pub const DIRECTIONS: [Hex; 6] = [EAST, SOUTH_EAST, SOUTH_WEST, WEST, NORTH_WEST, NORTH_EAST];
+
+/// Axial coordinates on a grid of pointy topped hexagons (rows run east - west). The third cube
+/// coordinate is implied by q + r + s = 0
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Copy)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub const fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// from cube coordinates, which must sum up to 0
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Self {
+        assert_eq!(q + r + s, 0, "cube coordinates must sum up to 0");
+        Hex::new(q, r)
+    }
+
+    /// the implied third cube coordinate
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// (q, r, s) cube coordinates
+    pub fn cube(self) -> (i32, i32, i32) {
+        (self.q, self.r, self.s())
+    }
+
+    /// number of steps between self and other
+    pub fn distance(self, other: Hex) -> i32 {
+        let d = self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// the six adjacent hexes in the order of DIRECTIONS
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        DIRECTIONS.into_iter().map(move |d| self + d)
+    }
+}
+
+/// A direction string contained something other than e, w, ne, nw, se and sw
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHexError {
+    /// byte offset of the first unparsable character
+    pub position: usize,
+}
+
+impl fmt::Display for ParseHexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid hex direction at position {}", self.position)
+    }
+}
+
+impl Error for ParseHexError {}
+
+/// parses directions written without separators like `nwwswee`, commas and whitespace between
+/// them are skipped
+pub fn parse_directions(s: &str) -> Result<Vec<Hex>, ParseHexError> {
+    let bytes = s.as_bytes();
+    let mut out = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let (dir, len) = match (bytes[i], bytes.get(i + 1)) {
+            (b',' | b' ' | b'\t' | b'\n' | b'\r', _) => {
+                i += 1;
+                continue;
+            }
+            (b'e', _) => (EAST, 1),
+            (b'w', _) => (WEST, 1),
+            (b'n', Some(b'e')) => (NORTH_EAST, 2),
+            (b'n', Some(b'w')) => (NORTH_WEST, 2),
+            (b's', Some(b'e')) => (SOUTH_EAST, 2),
+            (b's', Some(b'w')) => (SOUTH_WEST, 2),
+            _ => return Err(ParseHexError { position: i }),
+        };
+        out.push(dir);
+        i += len;
+    }
+    Ok(out)
+}
+
+/// follows the directions in `s` from ORIGIN and returns where they end
+pub fn walk(s: &str) -> Result<Hex, ParseHexError> {
+    Ok(parse_directions(s)?.into_iter().fold(ORIGIN, |a, b| a + b))
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.q += rhs.q;
+        self.r += rhs.r;
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> Self {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.q -= rhs.q;
+        self.r -= rhs.r;
+    }
+}
+
+/// An unbounded hex grid that only stores the cells that were set, everything else reads as
+/// `default`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexGrid<T> {
+    cells: HashMap<Hex, T>,
+    pub default: T,
+}
+
+impl<T> HexGrid<T> {
+    pub fn new(default: T) -> Self {
+        HexGrid {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    /// value at `h`, or the default if it was never set
+    pub fn get(&self, h: Hex) -> &T {
+        self.cells.get(&h).unwrap_or(&self.default)
+    }
+
+    /// stores `value` at `h` and returns the previous value if there was one
+    pub fn set(&mut self, h: Hex, value: T) -> Option<T> {
+        self.cells.insert(h, value)
+    }
+
+    pub fn remove(&mut self, h: Hex) -> Option<T> {
+        self.cells.remove(&h)
+    }
+
+    /// true if a value was set at `h`
+    pub fn contains(&self, h: Hex) -> bool {
+        self.cells.contains_key(&h)
+    }
+
+    /// number of stored cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// stored cells in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (Hex, &T)> {
+        self.cells.iter().map(|(&h, v)| (h, v))
+    }
+
+    /// the six adjacent hexes of `h` with their values
+    pub fn neighbors(&self, h: Hex) -> impl Iterator<Item = (Hex, &T)> {
+        h.neighbors().map(move |n| (n, self.get(n)))
+    }
+}
+
+impl<T: Clone> HexGrid<T> {
+    /// mutable access to the value at `h`, storing a copy of the default first if needed
+    pub fn get_mut(&mut self, h: Hex) -> &mut T {
+        self.cells.entry(h).or_insert_with(|| self.default.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_directions("nesew"),
+            Ok(vec![NORTH_EAST, SOUTH_EAST, WEST])
+        );
+        assert_eq!(walk("esew"), Ok(SOUTH_EAST));
+        assert_eq!(walk("nwwswee"), Ok(ORIGIN));
+        assert_eq!(walk("ne, ne,\nsw"), Ok(NORTH_EAST));
+        assert_eq!(walk("nex"), Err(ParseHexError { position: 2 }));
+        assert_eq!(walk("n"), Err(ParseHexError { position: 0 }));
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(walk("eee").unwrap().distance(ORIGIN), 3);
+        assert_eq!(walk("neneswsw").unwrap().distance(ORIGIN), 0);
+        assert_eq!(walk("nenesese").unwrap().distance(ORIGIN), 2);
+        assert_eq!((NORTH_WEST * 2 + EAST).distance(ORIGIN), 2);
+        for d in DIRECTIONS {
+            assert_eq!(d.distance(ORIGIN), 1);
+            assert_eq!(d.cube().0 + d.cube().1 + d.cube().2, 0);
+        }
+        assert_eq!(Hex::from_cube(2, -3, 1), Hex::new(2, -3));
+    }
+
+    #[test]
+    fn test_hex_grid() {
+        let mut floor = HexGrid::new(false);
+        for path in ["esew", "nwwswee", "ee", "e"] {
+            let tile = floor.get_mut(walk(path).unwrap());
+            *tile = !*tile;
+        }
+        let black = floor.iter().filter(|(_, &b)| b).count();
+        assert_eq!(black, 4);
+        assert!(!*floor.get(Hex::new(5, 5)));
+
+        // ORIGIN touches SOUTH_EAST and EAST
+        let around = floor.neighbors(ORIGIN).filter(|(_, &b)| b).count();
+        assert_eq!(around, 2);
+        assert_eq!(floor.set(ORIGIN, false), Some(true));
+        assert_eq!(floor.len(), 4);
+    }
+}
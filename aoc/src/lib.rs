@@ -1,9 +1,25 @@
 use std::fs;
 
+/// cellular automata (game of life and friends) on top of crate::grid
+pub mod automaton;
+/// cycle detection for simulations that run far too many steps to simulate
+pub mod cycle;
+/// orthogonal and diagonal directions with turning and parsing, on top of crate::point
+pub mod direction;
+/// lattice polygon area, boundary and interior point counts
+pub mod geometry;
+/// generic shortest path searches over arbitrary states
+pub mod graph;
 /// 2d grid interaction abstraction, which are common in aoc problems.
 pub mod grid;
+/// axial coordinates and sparse grids of hexagons
+pub mod hex;
+/// decoding of the block letters some puzzles draw as their answer
+pub mod ocr;
 /// (x,y) abstraction to interact with crate::grid
 pub mod point;
+/// 3d and 4d points and sparse sets of cubes
+pub mod voxel;
 
 pub fn lines_file(filename: &str) -> Vec<String> {
     fs::read_to_string(filename)
@@ -0,0 +1,277 @@
+use std::{collections::HashSet, error::Error, fmt, ops::RangeInclusive};
+
+use crate::{grid::Grid, point::Point};
+
+/// letters of the 4x6 font, one row per line
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// letters of the 6x10 font, one row per line
+#[rustfmt::skip]
+const FONT_10: [(char, &str); 15] = [
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OcrError {
+    /// no lit points at all
+    Empty,
+    /// the lit points are neither 6 nor 10 rows high
+    UnsupportedHeight(i32),
+    /// glyphs not in the font, as their index in the text and their rendering
+    UnknownGlyphs(Vec<(usize, String)>),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no lit points to decode"),
+            OcrError::UnsupportedHeight(h) => {
+                write!(f, "letters are {} rows high, expected 6 or 10", h)
+            }
+            OcrError::UnknownGlyphs(glyphs) => {
+                writeln!(f, "{} unrecognized glyph(s):", glyphs.len())?;
+                for (i, glyph) in glyphs {
+                    writeln!(f, "glyph {}:\n{}", i, glyph)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// removes leading and trailing empty columns from a glyph rendering
+fn trim(glyph: &str) -> String {
+    let rows = glyph.lines().collect::<Vec<_>>();
+    let lit = |x: usize| rows.iter().any(|r| r.as_bytes().get(x) == Some(&b'#'));
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let Some(start) = (0..width).find(|&x| lit(x)) else {
+        return String::new();
+    };
+    let end = (0..width).rev().find(|&x| lit(x)).unwrap_or(start);
+    rows.iter()
+        .map(|r| {
+            (start..=end)
+                .map(|x| *r.as_bytes().get(x).unwrap_or(&b'.') as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// decodes AoC block letters from a set of lit points, position and offset of the points do not
+/// matter. Letters of the 6 row font are on a 5 column pitch, letters of the 10 row font have to
+/// be separated by at least one empty column
+pub fn decode_points<I: IntoIterator<Item = Point>>(points: I) -> Result<String, OcrError> {
+    let lit = points.into_iter().collect::<HashSet<_>>();
+    let Some(&first) = lit.iter().next() else {
+        return Err(OcrError::Empty);
+    };
+    let (min, max) = lit.iter().fold((first, first), |(min, max), p| {
+        (
+            Point::new(min.x.min(p.x), min.y.min(p.y)),
+            Point::new(max.x.max(p.x), max.y.max(p.y)),
+        )
+    });
+
+    let height = max.y - min.y + 1;
+    let font: &[(char, &str)] = match height {
+        6 => &FONT_6,
+        10 => &FONT_10,
+        h => return Err(OcrError::UnsupportedHeight(h)),
+    };
+
+    if height == 10 {
+        // the large font is split at empty columns
+        let empty = |x: i32| (min.y..=max.y).all(|y| !lit.contains(&Point::new(x, y)));
+        let mut spans = vec![];
+        let mut start = None;
+        for x in min.x..=max.x + 1 {
+            match (start, x > max.x || empty(x)) {
+                (None, false) => start = Some(x),
+                (Some(s), true) => {
+                    spans.push((s, x - 1));
+                    start = None;
+                }
+                _ => (),
+            }
+        }
+        return read_glyphs(&lit, font, min.y..=max.y, spans);
+    }
+
+    // the small font sits on a fixed 5 column pitch, so wide letters like Y touch the next one.
+    // A first letter with empty leading columns, like I or J, moves min.x off the pitch
+    let pitch = |origin: i32| {
+        (origin..=max.x)
+            .step_by(5)
+            .map(|x| (x, x + 4))
+            .collect::<Vec<_>>()
+    };
+    let aligned = read_glyphs(&lit, font, min.y..=max.y, pitch(min.x));
+    if aligned.is_ok() {
+        return aligned;
+    }
+    (1..=2)
+        .map(|shift| read_glyphs(&lit, font, min.y..=max.y, pitch(min.x - shift)))
+        .find(Result::is_ok)
+        .unwrap_or(aligned)
+}
+
+/// matches the glyphs in the column spans `(from, to)` against `font`
+fn read_glyphs(
+    lit: &HashSet<Point>,
+    font: &[(char, &str)],
+    rows: RangeInclusive<i32>,
+    spans: Vec<(i32, i32)>,
+) -> Result<String, OcrError> {
+    let mut text = String::new();
+    let mut unknown = vec![];
+    for (i, (from, to)) in spans.into_iter().enumerate() {
+        let glyph = rows
+            .clone()
+            .map(|y| {
+                (from..=to)
+                    .map(|x| {
+                        if lit.contains(&Point::new(x, y)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        match font.iter().find(|(_, g)| trim(g) == trim(&glyph)) {
+            Some(&(c, _)) => text.push(c),
+            None => unknown.push((i, glyph)),
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs(unknown))
+    }
+}
+
+/// decodes AoC block letters from a grid, cells holding `lit` are set pixels
+pub fn decode_grid(grid: &Grid, lit: u8) -> Result<String, OcrError> {
+    decode_points(grid.positions(|&c| c == lit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// renders `text` in `font` the way the puzzles do, letters on a fixed pitch of 5 columns for
+    /// the small font and separated by an empty column for the large one
+    fn render(text: &str, font: &[(char, &str)]) -> String {
+        let (pitch, gap) = if font[0].1.lines().count() == 6 {
+            (5, "")
+        } else {
+            (6, ".")
+        };
+        let glyphs = text
+            .chars()
+            .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1)
+            .map(|g| {
+                g.lines()
+                    .map(|r| format!("{:.<pitch$}", r))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| g[y].as_str())
+                    .collect::<Vec<_>>()
+                    .join(gap)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_decode_small() {
+        let grid = Grid::parse(&render("HELLOJIYZ", &FONT_6));
+        assert_eq!(decode_grid(&grid, b'#'), Ok(String::from("HELLOJIYZ")));
+    }
+
+    #[test]
+    fn test_decode_fixed_pitch() {
+        // Y is 5 columns wide and touches the letter after it
+        let grid = Grid::parse(&render("YAY", &FONT_6));
+        assert_eq!(decode_grid(&grid, b'#'), Ok(String::from("YAY")));
+        // leading empty columns of the first letter do not throw off the pitch
+        let grid = Grid::parse(&render("IJYA", &FONT_6));
+        assert_eq!(decode_grid(&grid, b'#'), Ok(String::from("IJYA")));
+    }
+
+    #[test]
+    fn test_decode_large() {
+        let grid = Grid::parse(&render("NXGRAZ", &FONT_10));
+        assert_eq!(decode_grid(&grid, b'#'), Ok(String::from("NXGRAZ")));
+    }
+
+    #[test]
+    fn test_decode_offset_points() {
+        let grid = Grid::parse(&render("AB", &FONT_6));
+        let shifted = grid
+            .positions(|&c| c == b'#')
+            .map(|p| p + Point::new(-50, 17));
+        assert_eq!(decode_points(shifted), Ok(String::from("AB")));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(decode_points([]), Err(OcrError::Empty));
+        assert_eq!(
+            decode_grid(&Grid::parse("#\n#\n#"), b'#'),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+
+        let mut grid = Grid::parse(&render("ABC", &FONT_6));
+        grid[Point::new(7, 2)] = b'.';
+        let Err(OcrError::UnknownGlyphs(unknown)) = decode_grid(&grid, b'#') else {
+            panic!("expected unknown glyph");
+        };
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].0, 1);
+    }
+}
@@ -1,10 +1,19 @@
-use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    hash::{BuildHasherDefault, Hash, Hasher},
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign},
+};
 
 pub const ORIGIN: Point = Point::new(0, 0);
 pub const UP: Point = Point::new(0, -1);
 pub const DOWN: Point = Point::new(0, 1);
 pub const LEFT: Point = Point::new(-1, 0);
 pub const RIGHT: Point = Point::new(1, 0);
+/// the four orthogonal directions, clockwise starting at UP
+pub const CARDINAL: [Point; 4] = [UP, RIGHT, DOWN, LEFT];
 pub const DIAGONAL: [Point; 8] = [
     Point::new(-1, -1),
     UP,
@@ -16,19 +25,186 @@ pub const DIAGONAL: [Point; 8] = [
     Point::new(1, 1),
 ];
 
-#[derive(Debug, Default, Clone, PartialEq, Copy)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+/// A 2d point, `i32` by default. Use `Point<i64>` when coordinates outgrow `i32`. Points are
+/// ordered in reading order, top to bottom and then left to right
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Copy)]
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
-    pub const fn new(x: i32, y: i32) -> Self {
+/// Signed integer types usable as Point coordinates
+pub trait Coordinate:
+    Copy
+    + Ord
+    + Default
+    + Hash
+    + Debug
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + SubAssign
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + TryInto<usize, Error = TryFromIntError>
+{
+    const ZERO: Self;
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn div_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),+) => {
+        $(impl Coordinate for $t {
+            const ZERO: Self = 0;
+
+            #[inline]
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            #[inline]
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+
+            #[inline]
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+
+            #[inline]
+            fn div_euclid(self, rhs: Self) -> Self {
+                <$t>::div_euclid(self, rhs)
+            }
+        })+
+    };
+}
+
+coordinate!(i8, i16, i32, i64, i128, isize);
+
+/// greatest common divisor of `a` and `b`, never negative. gcd(0, 0) is 0
+pub fn gcd<T: Coordinate>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
 }
 
-impl Add for Point {
+impl<T: Coordinate> Point<T> {
+    /// wraps the point into the rectangle [0, width) x [0, height) using euclidean (python style)
+    /// modulo, so (-1, 0) wraps to (width - 1, 0) instead of staying negative
+    pub fn rem_euclid(self, width: T, height: T) -> Self {
+        Point::new(self.x.rem_euclid(width), self.y.rem_euclid(height))
+    }
+
+    /// euclidean division of both coordinates, the counterpart of Point::rem_euclid
+    pub fn div_euclid(self, width: T, height: T) -> Self {
+        Point::new(self.x.div_euclid(width), self.y.div_euclid(height))
+    }
+
+    /// taxicab distance between self and other
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// king's move distance, the larger of the coordinate differences
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// square of the euclidean distance, exact unlike the distance itself
+    pub fn distance_squared(self, other: Self) -> T {
+        let d = self - other;
+        d.x * d.x + d.y * d.y
+    }
+
+    /// -1, 0 or 1 per coordinate, turns any offset along a row, column or diagonal into a step
+    pub fn signum(self) -> Self {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn abs(self) -> Self {
+        Point::new(self.x.abs(), self.y.abs())
+    }
+
+    /// rotates by 90 degrees clockwise (as drawn, with y growing downwards) around `center`, so
+    /// UP turns into RIGHT
+    pub fn rotate90(self, center: Self) -> Self {
+        let d = self - center;
+        center + Point::new(-d.y, d.x)
+    }
+
+    /// rotates by 90 degrees counterclockwise around `center`, undoing Point::rotate90
+    pub fn rotate270(self, center: Self) -> Self {
+        let d = self - center;
+        center + Point::new(d.y, -d.x)
+    }
+
+    /// the smallest step along the same integer line, both coordinates divided by their gcd,
+    /// so (4, -6) becomes (2, -3). ORIGIN stays ORIGIN
+    pub fn reduced(self) -> Self {
+        let a = gcd(self.x, self.y);
+        if a == T::ZERO {
+            self
+        } else {
+            Point::new(self.x / a, self.y / a)
+        }
+    }
+
+    /// converts to a point with a wider coordinate type, like Point<i32> to Point<i64>
+    pub fn cast<U: From<T>>(self) -> Point<U> {
+        Point::new(self.x.into(), self.y.into())
+    }
+
+    /// converts to a point with a narrower coordinate type, failing if a coordinate overflows it
+    pub fn try_cast<U: TryFrom<T>>(self) -> Result<Point<U>, U::Error> {
+        Ok(Point::new(U::try_from(self.x)?, U::try_from(self.y)?))
+    }
+}
+
+impl<T: Ord> Ord for Point<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.y.cmp(&other.y).then_with(|| self.x.cmp(&other.x))
+    }
+}
+
+impl<T: Ord> PartialOrd for Point<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Coordinate> TryFrom<Point<T>> for (usize, usize) {
+    type Error = TryFromIntError;
+
+    /// (x, y) as indexes, negative coordinates are rejected instead of wrapping around
+    fn try_from(p: Point<T>) -> Result<Self, Self::Error> {
+        Ok((p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+impl<T: TryFrom<usize, Error = TryFromIntError>> TryFrom<(usize, usize)> for Point<T> {
+    type Error = TryFromIntError;
+
+    /// point from (x, y) indexes, failing if they overflow the coordinate type
+    fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
+        Ok(Point::new(x.try_into()?, y.try_into()?))
+    }
+}
+
+impl<T: Coordinate> Add for Point<T> {
     type Output = Self;
 
     #[inline]
@@ -37,7 +213,7 @@ impl Add for Point {
     }
 }
 
-impl AddAssign for Point {
+impl<T: Coordinate> AddAssign for Point<T> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
         self.x += rhs.x;
@@ -45,16 +221,16 @@ impl AddAssign for Point {
     }
 }
 
-impl Mul<i32> for Point {
+impl<T: Coordinate> Mul<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    fn mul(self, rhs: i32) -> Self {
+    fn mul(self, rhs: T) -> Self {
         Point::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Sub for Point {
+impl<T: Coordinate> Sub for Point<T> {
     type Output = Self;
 
     #[inline]
@@ -63,10 +239,151 @@ impl Sub for Point {
     }
 }
 
-impl SubAssign for Point {
+impl<T: Coordinate> SubAssign for Point<T> {
     #[inline]
     fn sub_assign(&mut self, rhs: Self) {
         self.x -= rhs.x;
         self.y -= rhs.y;
     }
 }
+
+/// A fast, non cryptographic hasher (the Fx hash used by rustc) for sets and maps of points in
+/// hot loops, where the default SipHash dominates the runtime
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PointHasher {
+    hash: u64,
+}
+
+impl PointHasher {
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for PointHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.add(i as u32 as u64);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+pub type PointBuildHasher = BuildHasherDefault<PointHasher>;
+/// HashSet of points using PointHasher, create it with `PointSet::default()`
+pub type PointSet<T = i32> = HashSet<Point<T>, PointBuildHasher>;
+/// HashMap keyed by points using PointHasher, create it with `PointMap::default()`
+pub type PointMap<V, T = i32> = HashMap<Point<T>, V, PointBuildHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_i64() {
+        let a = Point::new(3_000_000_000i64, -2);
+        let b = Point::new(-3_000_000_000, 5);
+        assert_eq!(a.manhattan(b), 6_000_000_007);
+        assert_eq!(a - b, Point::new(6_000_000_000, -7));
+        assert_eq!(b * 2 + a, Point::new(-3_000_000_000, 8));
+        assert_eq!(a.rem_euclid(7, 7), Point::new(3_000_000_000 % 7, 5));
+        assert_eq!(Point::new(10i64, -4).reduced(), Point::new(5, -2));
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(<(usize, usize)>::try_from(Point::new(3, 4)), Ok((3, 4)));
+        assert!(<(usize, usize)>::try_from(Point::new(-1, 4)).is_err());
+        assert!(<(usize, usize)>::try_from(Point::new(0i64, -4)).is_err());
+        assert_eq!(Point::<i32>::try_from((7, 8)), Ok(Point::new(7, 8)));
+        assert!(Point::<i32>::try_from((usize::MAX, 0)).is_err());
+
+        let wide: Point<i64> = RIGHT.cast();
+        assert_eq!(wide, Point::new(1, 0));
+        assert_eq!(wide.try_cast::<i32>(), Ok(RIGHT));
+        assert!(Point::new(1i64 << 40, 0).try_cast::<i32>().is_err());
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut points = vec![Point::new(2, 1), Point::new(0, 2), Point::new(1, 1), ORIGIN];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![ORIGIN, Point::new(1, 1), Point::new(2, 1), Point::new(0, 2)]
+        );
+
+        let mut heap = std::collections::BinaryHeap::from(points);
+        assert_eq!(heap.pop(), Some(Point::new(0, 2)));
+        assert!(Point::new(5, 0) < Point::new(0, 1));
+    }
+
+    #[test]
+    fn test_metrics() {
+        let (a, b) = (Point::new(1, 2), Point::new(4, -2));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!((b - a).signum(), Point::new(1, -1));
+        assert_eq!(Point::new(0, -7).signum(), UP);
+        assert_eq!((a - b).abs(), Point::new(3, 4));
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(UP.rotate90(ORIGIN), RIGHT);
+        assert_eq!(RIGHT.rotate90(ORIGIN), DOWN);
+        assert_eq!(LEFT.rotate270(ORIGIN), DOWN);
+
+        let center = Point::new(5, 5);
+        let p = Point::new(7, 4);
+        assert_eq!(p.rotate90(center), Point::new(6, 7));
+        assert_eq!(p.rotate90(center).rotate270(center), p);
+        let full = (0..4).fold(p, |q, _| q.rotate90(center));
+        assert_eq!(full, p);
+    }
+
+    #[test]
+    fn test_point_set() {
+        let mut set = PointSet::default();
+        for y in -50..50 {
+            for x in -50..50 {
+                set.insert(Point::new(x, y));
+            }
+        }
+        assert_eq!(set.len(), 10_000);
+        assert!(set.contains(&Point::new(-50, 49)));
+        assert!(!set.contains(&Point::new(50, 0)));
+
+        let mut map: PointMap<u32, i64> = PointMap::default();
+        *map.entry(Point::new(1 << 40, 3)).or_default() += 2;
+        assert_eq!(map[&Point::new(1 << 40, 3)], 2);
+    }
+}
@@ -0,0 +1,328 @@
+use std::{
+    collections::HashSet,
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
+};
+
+/// the six face neighbor directions
+pub const FACES: [Point3; 6] = [
+    Point3::new(1, 0, 0),
+    Point3::new(-1, 0, 0),
+    Point3::new(0, 1, 0),
+    Point3::new(0, -1, 0),
+    Point3::new(0, 0, 1),
+    Point3::new(0, 0, -1),
+];
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Copy)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Copy)]
+pub struct Point4 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub w: i32,
+}
+
+impl Point3 {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// taxicab distance between self and other
+    pub fn manhattan(self, other: Point3) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// the 6 points sharing a face with self
+    pub fn faces(self) -> impl Iterator<Item = Point3> {
+        FACES.into_iter().map(move |d| self + d)
+    }
+
+    /// the 26 points sharing a face, edge or corner with self
+    pub fn adjacent(self) -> impl Iterator<Item = Point3> {
+        (-1..=1)
+            .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| Point3::new(x, y, z))))
+            .filter(|&d| d != Point3::default())
+            .map(move |d| self + d)
+    }
+
+    /// smallest and largest corner of the box containing all points, None if there are none
+    pub fn bounds<I: IntoIterator<Item = Point3>>(points: I) -> Option<(Point3, Point3)> {
+        points.into_iter().fold(None, |acc, p| {
+            let (min, max) = acc.unwrap_or((p, p));
+            Some((
+                Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            ))
+        })
+    }
+}
+
+impl Point4 {
+    pub const fn new(x: i32, y: i32, z: i32, w: i32) -> Self {
+        Point4 { x, y, z, w }
+    }
+
+    /// taxicab distance between self and other
+    pub fn manhattan(self, other: Point4) -> i32 {
+        (self.x - other.x).abs()
+            + (self.y - other.y).abs()
+            + (self.z - other.z).abs()
+            + (self.w - other.w).abs()
+    }
+
+    /// the 80 points within one step on every axis of self
+    pub fn adjacent(self) -> impl Iterator<Item = Point4> {
+        Point3::default()
+            .adjacent()
+            .chain([Point3::default()])
+            .flat_map(|d| (-1..=1).map(move |w| Point4::new(d.x, d.y, d.z, w)))
+            .filter(|&d| d != Point4::default())
+            .map(move |d| self + d)
+    }
+
+    /// smallest and largest corner of the box containing all points, None if there are none
+    pub fn bounds<I: IntoIterator<Item = Point4>>(points: I) -> Option<(Point4, Point4)> {
+        points.into_iter().fold(None, |acc, p| {
+            let (min, max) = acc.unwrap_or((p, p));
+            Some((
+                Point4::new(
+                    min.x.min(p.x),
+                    min.y.min(p.y),
+                    min.z.min(p.z),
+                    min.w.min(p.w),
+                ),
+                Point4::new(
+                    max.x.max(p.x),
+                    max.y.max(p.y),
+                    max.z.max(p.z),
+                    max.w.max(p.w),
+                ),
+            ))
+        })
+    }
+}
+
+/// componentwise Add, AddAssign, Sub, SubAssign and scalar Mul<i32>, like crate::point::Point
+macro_rules! ops {
+    ($t:ident, $($f:ident),+) => {
+        impl Add for $t {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                $t { $($f: self.$f + rhs.$f),+ }
+            }
+        }
+
+        impl AddAssign for $t {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$f += rhs.$f;)+
+            }
+        }
+
+        impl Mul<i32> for $t {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: i32) -> Self {
+                $t { $($f: self.$f * rhs),+ }
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                $t { $($f: self.$f - rhs.$f),+ }
+            }
+        }
+
+        impl SubAssign for $t {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$f -= rhs.$f;)+
+            }
+        }
+    };
+}
+
+ops!(Point3, x, y, z);
+ops!(Point4, x, y, z, w);
+
+/// A sparse set of unit cubes
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VoxelSet {
+    cells: HashSet<Point3>,
+}
+
+impl VoxelSet {
+    pub fn new() -> Self {
+        VoxelSet::default()
+    }
+
+    /// adds a cube, returns false if it was already present
+    pub fn insert(&mut self, p: Point3) -> bool {
+        self.cells.insert(p)
+    }
+
+    pub fn remove(&mut self, p: Point3) -> bool {
+        self.cells.remove(&p)
+    }
+
+    pub fn contains(&self, p: Point3) -> bool {
+        self.cells.contains(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// cubes in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = Point3> + '_ {
+        self.cells.iter().copied()
+    }
+
+    pub fn bounds(&self) -> Option<(Point3, Point3)> {
+        Point3::bounds(self.iter())
+    }
+
+    /// number of cube faces not touching another cube, air pockets included
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .flat_map(Point3::faces)
+            .filter(|&p| !self.contains(p))
+            .count()
+    }
+
+    /// the empty cells connected to the outside by face steps, limited to the bounding box grown
+    /// by one in every direction
+    pub fn exterior(&self) -> HashSet<Point3> {
+        let Some((min, max)) = self.bounds() else {
+            return HashSet::new();
+        };
+        let one = Point3::new(1, 1, 1);
+        let (min, max) = (min - one, max + one);
+        let inside = |p: Point3| {
+            (min.x..=max.x).contains(&p.x)
+                && (min.y..=max.y).contains(&p.y)
+                && (min.z..=max.z).contains(&p.z)
+        };
+
+        let mut seen = HashSet::from([min]);
+        let mut stack = vec![min];
+        while let Some(p) = stack.pop() {
+            for n in p.faces() {
+                if inside(n) && !self.contains(n) && seen.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        seen
+    }
+
+    /// number of cube faces reachable from the outside, air pockets excluded
+    pub fn exterior_surface_area(&self) -> usize {
+        let exterior = self.exterior();
+        self.iter()
+            .flat_map(Point3::faces)
+            .filter(|p| exterior.contains(p))
+            .count()
+    }
+}
+
+impl FromIterator<Point3> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Point3>>(iter: I) -> Self {
+        VoxelSet {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DROPLET: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    fn parse(s: &str) -> VoxelSet {
+        s.lines()
+            .map(|l| {
+                let v = l.split(',').map(|n| n.parse().unwrap()).collect::<Vec<_>>();
+                Point3::new(v[0], v[1], v[2])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let pair = parse("1,1,1\n2,1,1");
+        assert_eq!(pair.surface_area(), 10);
+        assert_eq!(pair.exterior_surface_area(), 10);
+
+        let droplet = parse(DROPLET);
+        assert_eq!(droplet.surface_area(), 64);
+        assert_eq!(droplet.exterior_surface_area(), 58);
+        assert_eq!(
+            droplet.bounds(),
+            Some((Point3::new(1, 1, 1), Point3::new(3, 3, 6)))
+        );
+        assert_eq!(VoxelSet::new().exterior_surface_area(), 0);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p.faces().count(), 6);
+        assert!(p.faces().all(|n| n.manhattan(p) == 1));
+        let around = p.adjacent().collect::<HashSet<_>>();
+        assert_eq!(around.len(), 26);
+        assert!(!around.contains(&p));
+
+        let q = Point4::new(0, 0, 0, 1);
+        let around = q.adjacent().collect::<HashSet<_>>();
+        assert_eq!(around.len(), 80);
+        assert!(!around.contains(&q));
+        assert!(around.contains(&Point4::new(-1, 1, -1, 0)));
+    }
+
+    #[test]
+    fn test_ops() {
+        let mut p = Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2;
+        assert_eq!(p, Point3::new(3, 4, 5));
+        p -= Point3::new(3, 4, 5);
+        assert_eq!(p, Point3::default());
+
+        let q = Point4::new(1, -2, 3, -4);
+        assert_eq!(q - q * 2, Point4::new(-1, 2, -3, 4));
+        assert_eq!(q.manhattan(Point4::default()), 10);
+        assert_eq!(
+            Point4::bounds([q, Point4::new(0, 0, 5, 0)]),
+            Some((Point4::new(0, -2, 3, -4), Point4::new(1, 0, 5, 0)))
+        );
+    }
+}